@@ -0,0 +1,258 @@
+use rand::Rng;
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::time::Instant;
+
+use crate::difficulty::Difficulty;
+use crate::direction::Direction;
+use crate::grid::{Grid, PlacementResult, Slot};
+use crate::word::Word;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchStatus {
+    Solved,
+    Exhausted,
+    OutOfBudget,
+}
+
+#[derive(Debug)]
+pub struct SearchOutcome {
+    pub grid: Grid,
+    pub placed: Vec<PlacementResult>,
+    pub unplaced: Vec<Word>,
+    pub status: SearchStatus,
+}
+
+pub struct Backtracker<'a> {
+    difficulty: &'a Difficulty,
+    directions: Vec<Direction>,
+    max_steps: usize,
+    deadline: Option<Instant>,
+    steps: usize,
+    out_of_budget: bool,
+    best: Option<(Grid, Vec<(usize, PlacementResult)>)>,
+    exact_overlap: Option<usize>,
+}
+
+impl<'a> Backtracker<'a> {
+    pub fn new(difficulty: &'a Difficulty, max_steps: usize, deadline: Option<Instant>) -> Self {
+        Self {
+            difficulty,
+            directions: difficulty.allowed_directions(),
+            max_steps,
+            deadline,
+            steps: 0,
+            out_of_budget: false,
+            best: None,
+            exact_overlap: None,
+        }
+    }
+
+    /// Exige que as palavras compartilhem exatamente `overlap` letras no total, para deixar um
+    /// número certo de células livres.
+    pub fn with_exact_overlap(mut self, overlap: Option<usize>) -> Self {
+        self.exact_overlap = overlap;
+        self
+    }
+
+    pub fn search<R: Rng>(mut self, grid: Grid, words: &[Word], rng: &mut R) -> SearchOutcome {
+        let mut grid = grid;
+        let mut placed = Vec::new();
+        let mut remaining: Vec<usize> = (0..words.len()).collect();
+
+        if self.solve(&mut grid, words, &mut remaining, &mut placed, rng) {
+            return SearchOutcome {
+                grid,
+                placed: placed.into_iter().map(|(_, p)| p).collect(),
+                unplaced: Vec::new(),
+                status: SearchStatus::Solved,
+            };
+        }
+
+        let status = if self.out_of_budget {
+            SearchStatus::OutOfBudget
+        } else {
+            SearchStatus::Exhausted
+        };
+
+        let (mut grid, mut placed) = self.best.take().unwrap_or((grid, Vec::new()));
+        let mut unplaced = Vec::new();
+
+        for (index, word) in words.iter().enumerate() {
+            if placed.iter().any(|(i, _)| *i == index) {
+                continue;
+            }
+            let mut slots = grid.candidate_slots(word, &self.directions);
+            self.difficulty.shuffle_slots(&mut slots, rng);
+            match slots.first() {
+                Some(slot) => placed.push((index, grid.place_word(word, slot.row, slot.col, slot.direction))),
+                None => unplaced.push(word.clone()),
+            }
+        }
+
+        SearchOutcome {
+            grid,
+            placed: placed.into_iter().map(|(_, p)| p).collect(),
+            unplaced,
+            status,
+        }
+    }
+
+    fn solve<R: Rng>(
+        &mut self,
+        grid: &mut Grid,
+        words: &[Word],
+        remaining: &mut Vec<usize>,
+        placed: &mut Vec<(usize, PlacementResult)>,
+        rng: &mut R,
+    ) -> bool {
+        if let Some(required) = self.exact_overlap {
+            let overlap = shared_letters(placed);
+            // Cada palavra restante precisa ocupar ao menos uma célula nova.
+            let still_possible: usize = remaining.iter().map(|&i| words[i].len() - 1).sum();
+            if overlap > required || overlap + still_possible < required {
+                return false;
+            }
+            if remaining.is_empty() {
+                return overlap == required;
+            }
+        }
+        if remaining.is_empty() {
+            return true;
+        }
+
+        if self.best.as_ref().is_none_or(|(_, best)| placed.len() > best.len()) {
+            self.best = Some((grid.clone(), placed.clone()));
+        }
+
+        let mut chosen: Option<(usize, Vec<Slot>)> = None;
+        for (pos, &index) in remaining.iter().enumerate() {
+            let slots = grid.candidate_slots(&words[index], &self.directions);
+            if slots.is_empty() {
+                return false;
+            }
+            if chosen.as_ref().is_none_or(|(_, best)| slots.len() < best.len()) {
+                chosen = Some((pos, slots));
+            }
+        }
+
+        let (pos, mut slots) = match chosen {
+            Some(choice) => choice,
+            None => return false,
+        };
+        let index = remaining.remove(pos);
+        let word = &words[index];
+        self.difficulty.shuffle_slots(&mut slots, rng);
+
+        if let Some(required) = self.exact_overlap {
+            let missing = required - shared_letters(placed);
+            slots.retain(|&slot| grid.overlap_count(word, slot) <= missing);
+            slots.sort_by_key(|&slot| Reverse(grid.overlap_count(word, slot)));
+        } else if let Some((min, max)) = self.difficulty.overlap_range() {
+            let current: Vec<PlacementResult> = placed.iter().map(|(_, p)| p.clone()).collect();
+            let ratio = grid.crossing_ratio(&current);
+            if ratio < min {
+                slots.sort_by_key(|&slot| Reverse(grid.overlap_count(word, slot)));
+            } else if ratio >= max {
+                slots.sort_by_key(|&slot| grid.overlap_count(word, slot));
+            }
+        }
+
+        for slot in slots {
+            if self.budget_exceeded() {
+                break;
+            }
+            self.steps += 1;
+
+            let saved = grid.clone();
+            placed.push((index, grid.place_word(word, slot.row, slot.col, slot.direction)));
+
+            if self.solve(grid, words, remaining, placed, rng) {
+                return true;
+            }
+
+            placed.pop();
+            *grid = saved;
+        }
+
+        remaining.insert(pos, index);
+        false
+    }
+
+    fn budget_exceeded(&mut self) -> bool {
+        if self.out_of_budget {
+            return true;
+        }
+        let over_steps = self.steps >= self.max_steps;
+        let over_time = self.deadline.is_some_and(|deadline| Instant::now() >= deadline);
+        if over_steps || over_time {
+            self.out_of_budget = true;
+        }
+        self.out_of_budget
+    }
+}
+
+/// Letras que caíram sobre células já ocupadas por outras palavras.
+fn shared_letters(placed: &[(usize, PlacementResult)]) -> usize {
+    let letters: usize = placed.iter().map(|(_, p)| p.word.len()).sum();
+    let cells: HashSet<(usize, usize)> = placed.iter().flat_map(|(_, p)| p.positions()).collect();
+    letters - cells.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::seeded_rng;
+
+    fn words(list: &[&str]) -> Vec<Word> {
+        list.iter().map(|w| Word::new(w)).collect()
+    }
+
+    fn reads(grid: &Grid, placement: &PlacementResult) -> String {
+        placement.positions().into_iter().filter_map(|(row, col)| grid.get(row, col)).collect()
+    }
+
+    #[test]
+    fn fills_a_grid_with_no_room_to_spare() {
+        let difficulty = Difficulty::easy();
+        let list = words(&["ABC", "DEF", "GHI"]);
+        let outcome = Backtracker::new(&difficulty, 10_000, None).search(Grid::new(3, 3), &list, &mut seeded_rng(7));
+        assert_eq!(outcome.status, SearchStatus::Solved);
+        assert!(outcome.unplaced.is_empty());
+        for placement in &outcome.placed {
+            assert_eq!(reads(&outcome.grid, placement), placement.word.normalized);
+        }
+    }
+
+    #[test]
+    fn proves_impossible_when_the_words_cannot_fit() {
+        let difficulty = Difficulty::easy();
+        let list = words(&["ABC", "DEF", "GHI", "JKL"]);
+        let outcome = Backtracker::new(&difficulty, 1_000_000, None).search(Grid::new(3, 3), &list, &mut seeded_rng(7));
+        assert_eq!(outcome.status, SearchStatus::Exhausted);
+        assert_eq!(outcome.placed.len() + outcome.unplaced.len(), 4);
+        assert!(!outcome.unplaced.is_empty());
+    }
+
+    #[test]
+    fn stops_when_the_step_budget_runs_out() {
+        let difficulty = Difficulty::easy();
+        let list = words(&["ABC", "DEF", "GHI"]);
+        let outcome = Backtracker::new(&difficulty, 1, None).search(Grid::new(3, 3), &list, &mut seeded_rng(7));
+        assert_eq!(outcome.status, SearchStatus::OutOfBudget);
+    }
+
+    #[test]
+    fn honours_an_exact_overlap() {
+        let difficulty = Difficulty::medium();
+        let list = words(&["PIZZA", "PASTA", "SAL"]);
+        for required in [0, 1, 2] {
+            let outcome = Backtracker::new(&difficulty, 100_000, None)
+                .with_exact_overlap(Some(required))
+                .search(Grid::new(5, 5), &list, &mut seeded_rng(3));
+            assert_eq!(outcome.status, SearchStatus::Solved, "{} letras", required);
+            let placed: Vec<(usize, PlacementResult)> = outcome.placed.into_iter().enumerate().collect();
+            assert_eq!(shared_letters(&placed), required);
+        }
+    }
+}
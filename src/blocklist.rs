@@ -0,0 +1,96 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::grid::PlacementResult;
+use crate::word::Word;
+
+const BUNDLED_PORTUGUESE: &str = include_str!("../assets/blocklist/pt.txt");
+const BUNDLED_ENGLISH: &str = include_str!("../assets/blocklist/en.txt");
+const MIN_BLOCKED_LEN: usize = 3;
+
+/// Palavras que o preenchimento não pode formar por acaso, em nenhuma das oito direções.
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    words: Vec<Word>,
+}
+
+/// Resultado da varredura: palavras desfeitas trocando letras de preenchimento e palavras
+/// que continuaram no grid por serem formadas só por letras fixas, das palavras do puzzle
+/// (ou da mensagem secreta) ou passando por alguma isca.
+#[derive(Debug, Clone, Default)]
+pub struct BlocklistReport {
+    pub avoided: Vec<PlacementResult>,
+    pub remaining: Vec<PlacementResult>,
+    pub decoys: Vec<PlacementResult>,
+}
+
+impl Blocklist {
+    /// Listas embutidas em português e inglês.
+    pub fn bundled() -> Self {
+        let mut blocklist = Self::default();
+        blocklist.extend_from_str(BUNDLED_PORTUGUESE);
+        blocklist.extend_from_str(BUNDLED_ENGLISH);
+        blocklist
+    }
+
+    /// Acrescenta as palavras de um arquivo, uma por linha; `#` inicia um comentário.
+    pub fn extend_from_file(&mut self, path: &Path) -> io::Result<()> {
+        let content = fs::read_to_string(path)?;
+        self.extend_from_str(content.trim_start_matches('\u{feff}'));
+        Ok(())
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Acrescenta palavras de um texto no mesmo formato dos arquivos; palavras com menos de
+    /// três letras e repetidas são ignoradas.
+    pub fn extend_from_str(&mut self, content: &str) {
+        for line in content.lines() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            let word = Word::new(line);
+            if word.len() >= MIN_BLOCKED_LEN && !self.words.iter().any(|w| w.normalized == word.normalized) {
+                self.words.push(word);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(blocklist: &Blocklist) -> Vec<&str> {
+        blocklist.words().iter().map(|w| w.normalized.as_str()).collect()
+    }
+
+    #[test]
+    fn skips_comments_short_words_and_repeats() {
+        let mut blocklist = Blocklist::default();
+        blocklist.extend_from_str("# lista da escola\nbobo  # comentário\nBÔBO\noi\n\ncaça\n");
+        assert_eq!(normalized(&blocklist), vec!["BOBO", "CACA"]);
+    }
+
+    #[test]
+    fn user_files_extend_the_bundled_lists() {
+        let bundled = Blocklist::bundled();
+        assert!(!bundled.is_empty());
+        assert!(bundled.words().iter().all(|w| w.len() >= MIN_BLOCKED_LEN));
+
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-bloqueio-{}.txt", std::process::id()));
+        fs::write(&path, "\u{feff}abacaxi\n").unwrap();
+        let mut blocklist = bundled.clone();
+        let result = blocklist.extend_from_file(&path);
+        fs::remove_file(&path).unwrap();
+        result.unwrap();
+        assert_eq!(normalized(&blocklist).last(), Some(&"ABACAXI"));
+        assert_eq!(blocklist.words().len(), bundled.words().len() + 1);
+        assert!(Blocklist::default().extend_from_file(&path).is_err());
+    }
+}
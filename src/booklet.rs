@@ -0,0 +1,184 @@
+use clap::ValueEnum;
+use serde::Deserialize;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{parse_grid_size, DifficultyLevel, ShapeArg};
+use crate::difficulty::Difficulty;
+use crate::input::PuzzleInput;
+use crate::mask::Mask;
+use crate::wordlist;
+
+#[derive(Debug, Deserialize)]
+pub struct BookletManifest {
+    #[serde(alias = "titulo")]
+    pub title: String,
+    pub puzzles: Vec<BookletEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookletEntry {
+    #[serde(alias = "titulo")]
+    pub title: String,
+    #[serde(default, alias = "palavras")]
+    pub words: Vec<String>,
+    #[serde(default, alias = "arquivo_palavras")]
+    pub words_file: Option<PathBuf>,
+    #[serde(default, alias = "dificuldade")]
+    pub difficulty: Option<String>,
+    #[serde(default, alias = "tamanho")]
+    pub size: Option<SizeSpec>,
+    #[serde(default, alias = "formato")]
+    pub shape: Option<String>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum SizeSpec {
+    Side(usize),
+    Text(String),
+}
+
+impl BookletManifest {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let manifest: Self = serde_json::from_str(content.trim_start_matches('\u{feff}'))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        if manifest.puzzles.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "o manifesto não tem nenhum puzzle"));
+        }
+        Ok(manifest)
+    }
+
+    /// Monta a entrada de cada puzzle. Arquivos de palavras são relativos ao diretório do
+    /// manifesto; sem seed própria, o puzzle N usa `base_seed + N`.
+    pub fn puzzle_inputs(&self, base_dir: &Path, base_seed: Option<u64>) -> Result<Vec<PuzzleInput>, String> {
+        self.puzzles
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let seed = entry.seed.or_else(|| base_seed.map(|s| s.wrapping_add(i as u64)));
+                entry.to_input(base_dir, seed).map_err(|e| format!("puzzle {} (\"{}\"): {}", i + 1, entry.title, e))
+            })
+            .collect()
+    }
+}
+
+impl BookletEntry {
+    fn to_input(&self, base_dir: &Path, seed: Option<u64>) -> Result<PuzzleInput, String> {
+        let mut words = self.words.clone();
+        if let Some(file) = &self.words_file {
+            let path = base_dir.join(file);
+            let entries = wordlist::load_word_list(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            words.extend(entries.into_iter().map(|entry| entry.word));
+        }
+        let words = wordlist::dedup_words(words);
+        if words.is_empty() {
+            return Err("nenhuma palavra (use \"words\" ou \"words_file\")".to_string());
+        }
+
+        let difficulty = match &self.difficulty {
+            Some(name) => match DifficultyLevel::from_str(name, true) {
+                Ok(level) => level.to_difficulty(),
+                Err(_) => {
+                    let path = base_dir.join(name);
+                    if !path.exists() {
+                        return Err(format!("dificuldade desconhecida: '{}'", name));
+                    }
+                    Difficulty::load(&path).map_err(|e| format!("{}: {}", path.display(), e))?
+                }
+            },
+            None => Difficulty::medium(),
+        };
+
+        let (width, height) = match &self.size {
+            Some(SizeSpec::Side(side)) => parse_grid_size(&side.to_string())?,
+            Some(SizeSpec::Text(text)) => parse_grid_size(text)?,
+            None => (15, 15),
+        };
+
+        let mask = match &self.shape {
+            Some(name) => {
+                let shape = ShapeArg::from_str(name, true).map_err(|_| format!("formato desconhecido: '{}'", name))?;
+                Some(Mask::from_shape(shape.to_shape(), width, height))
+            }
+            None => None,
+        };
+
+        Ok(PuzzleInput {
+            title: self.title.clone(),
+            difficulty,
+            width,
+            height,
+            mask,
+            seed,
+            words,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(json: &str) -> BookletManifest {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn reads_portuguese_fields_sizes_and_shapes() {
+        let manifest = manifest(
+            r#"{"titulo": "Pacote", "puzzles": [
+                {"titulo": "Frutas", "palavras": ["uva", "Uva", "pera"], "dificuldade": "facil", "tamanho": 12},
+                {"title": "Cores", "words": ["azul"], "size": "14x10", "shape": "coracao", "seed": 7}
+            ]}"#,
+        );
+        let inputs = manifest.puzzle_inputs(Path::new("."), Some(100)).unwrap();
+
+        assert_eq!(inputs[0].words, vec!["uva", "pera"]);
+        assert_eq!((inputs[0].width, inputs[0].height), (12, 12));
+        assert_eq!(inputs[0].difficulty.name, Difficulty::easy().name);
+        assert_eq!(inputs[0].seed, Some(100));
+        assert!(inputs[0].mask.is_none());
+
+        assert_eq!((inputs[1].width, inputs[1].height), (14, 10));
+        assert_eq!(inputs[1].seed, Some(7));
+        assert!(inputs[1].mask.is_some());
+    }
+
+    #[test]
+    fn seeds_follow_the_puzzle_position() {
+        let manifest = manifest(r#"{"title": "P", "puzzles": [{"title": "A", "words": ["a"]}, {"title": "B", "words": ["b"]}]}"#);
+        let seeds: Vec<_> = manifest.puzzle_inputs(Path::new("."), Some(10)).unwrap().iter().map(|i| i.seed).collect();
+        assert_eq!(seeds, vec![Some(10), Some(11)]);
+        assert!(manifest.puzzle_inputs(Path::new("."), None).unwrap().iter().all(|i| i.seed.is_none()));
+    }
+
+    #[test]
+    fn word_files_are_relative_to_the_manifest() {
+        let dir = std::env::temp_dir().join(format!("pizza-wordsearch-caderno-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("frutas.txt"), "maçã\nbanana\n").unwrap();
+        let manifest = manifest(r#"{"title": "P", "puzzles": [{"title": "A", "words": ["uva"], "arquivo_palavras": "frutas.txt"}]}"#);
+        let inputs = manifest.puzzle_inputs(&dir, None);
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(inputs.unwrap()[0].words, vec!["uva", "maçã", "banana"]);
+    }
+
+    #[test]
+    fn reports_which_puzzle_is_invalid() {
+        for entry in [
+            r#"{"title": "Vazio"}"#,
+            r#"{"title": "Nível", "words": ["a"], "difficulty": "impossivel"}"#,
+            r#"{"title": "Forma", "words": ["a"], "shape": "estrela"}"#,
+            r#"{"title": "Tamanho", "words": ["a"], "size": "0x4"}"#,
+        ] {
+            let manifest = manifest(&format!(r#"{{"title": "P", "puzzles": [{}]}}"#, entry));
+            let error = manifest.puzzle_inputs(Path::new("."), None).unwrap_err();
+            assert!(error.starts_with("puzzle 1"), "{}", error);
+        }
+    }
+}
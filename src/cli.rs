@@ -0,0 +1,491 @@
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+use crate::difficulty::Difficulty;
+use crate::filler::{FillerSource, FillerStrategy, Language};
+use crate::generator::PlacementStrategy;
+use crate::mask::Shape;
+use crate::render::{AnswerStyle, Orientation, OutputFormat, PageSize, MIN_PAGE_SIDE_MM};
+
+#[derive(Debug, Parser)]
+#[command(name = "pizza-wordsearch", version, about = "Gerador de caça-palavras determinístico")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Gera um caça-palavras sem interação, a partir de flags
+    Generate(Box<GenerateArgs>),
+    /// Gera os PDFs (ou SVG/PNG/HTML) a partir de um arquivo de puzzle salvo, sem gerar novamente
+    Render(RenderArgs),
+    /// Gera um caderno em PDF com vários puzzles e os gabaritos no final
+    Booklet(BookletArgs),
+    /// Joga um puzzle salvo no terminal, com cursor, cronômetro e pontuação
+    Play(PlayArgs),
+    /// Procura palavras em um grid existente
+    Solve(SolveArgs),
+    /// Confere se cada palavra aparece exatamente uma vez no grid
+    Verify(SolveArgs),
+    /// Assistente interativo (padrão quando nenhum subcomando é informado)
+    Interactive,
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// Título do caça-palavras
+    #[arg(short, long)]
+    pub title: String,
+
+    /// Nível de dificuldade (facil, medio, dificil) ou arquivo JSON com um perfil próprio
+    #[arg(short, long, default_value = "medio", value_parser = parse_difficulty)]
+    pub difficulty: Difficulty,
+
+    /// Direções permitidas com pesos, substituindo as do perfil: "Horizontal:3,Vertical:1"
+    #[arg(long)]
+    pub directions: Option<String>,
+
+    /// Tamanho do grid: lado (15) ou largura x altura (20x10)
+    #[arg(short, long, default_value = "15", value_parser = parse_grid_size)]
+    pub size: (usize, usize),
+
+    /// Formato do grid
+    #[arg(long, value_enum, conflicts_with = "mask_file")]
+    pub shape: Option<ShapeArg>,
+
+    /// Arquivo de máscara em ASCII ('#' = célula ativa, '.' = vazia); define as dimensões do grid
+    #[arg(long)]
+    pub mask_file: Option<PathBuf>,
+
+    /// Palavras separadas por vírgula
+    #[arg(short, long, value_delimiter = ',')]
+    pub words: Vec<String>,
+
+    /// Arquivo de palavras: TXT (uma por linha ou por vírgula), CSV (palavra,dica,categoria) ou JSON
+    #[arg(long)]
+    pub words_file: Option<PathBuf>,
+
+    /// Estratégia de posicionamento das palavras
+    #[arg(long, value_enum, default_value_t = StrategyArg::Aleatoria)]
+    pub strategy: StrategyArg,
+
+    /// Limite de passos da busca com backtracking
+    #[arg(long, default_value_t = 200_000)]
+    pub max_steps: usize,
+
+    /// Limite de tempo da busca com backtracking, em milissegundos
+    #[arg(long)]
+    pub time_limit_ms: Option<u64>,
+
+    /// Proporção-alvo de letras compartilhadas entre palavras (0.0 a 1.0)
+    #[arg(long)]
+    pub overlap: Option<f32>,
+
+    /// Iscas (palavras quase certas, como "QUEIJ") por palavra posicionada, de 0.0 a 1.0
+    /// (padrão: 0.5 no nível difícil e 0 nos demais)
+    #[arg(long)]
+    pub decoys: Option<f32>,
+
+    /// Letras do preenchimento: pt, en, es, it, fr, "palavras" (frequência das próprias
+    /// palavras) ou um arquivo com "letra peso" por linha
+    #[arg(long, default_value = "pt", value_parser = parse_filler)]
+    pub filler: FillerSource,
+
+    /// Como sortear as letras: uma a uma pela frequência, ou continuando as letras vizinhas
+    /// com bigramas para formar fragmentos parecidos com palavras (padrão: a do perfil)
+    #[arg(long, value_enum)]
+    pub filler_strategy: Option<FillerStrategyArg>,
+
+    /// Arquivo com palavras a evitar no preenchimento, uma por linha, somado às listas
+    /// embutidas em português e inglês
+    #[arg(long, conflicts_with = "no_blocklist")]
+    pub blocklist: Option<PathBuf>,
+
+    /// Não verifica palavras bloqueadas no preenchimento
+    #[arg(long)]
+    pub no_blocklist: bool,
+
+    /// Mensagem secreta escrita com as letras que sobram, lidas da esquerda para a direita;
+    /// as palavras se cruzam o necessário para sobrarem exatamente as células da mensagem
+    #[arg(long, conflicts_with = "decoys")]
+    pub message: Option<String>,
+
+    /// Seed para gerar o mesmo grid novamente (padrão: aleatória)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Diretório de saída dos PDFs e do puzzle.json (padrão: pdf/{título})
+    #[arg(short, long)]
+    pub out: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+
+    /// Não falha quando alguma palavra não puder ser posicionada
+    #[arg(long)]
+    pub allow_discarded: bool,
+
+    /// Mostra detalhes da geração, como as palavras bloqueadas que foram evitadas
+    #[arg(short, long)]
+    pub verbose: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    /// Arquivo de puzzle (.json) salvo pelo generate
+    pub puzzle: PathBuf,
+
+    /// Substitui o título salvo no arquivo
+    #[arg(short, long)]
+    pub title: Option<String>,
+
+    /// Diretório de saída (padrão: pdf/{título})
+    #[arg(short, long)]
+    pub out: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct PlayArgs {
+    /// Arquivo de puzzle (.json) ou sessão salva para continuar
+    pub file: PathBuf,
+
+    /// Onde salvar a sessão (padrão: sessao.json no diretório do puzzle)
+    #[arg(long)]
+    pub session: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct BookletArgs {
+    /// Manifesto JSON com o título do caderno e a lista de puzzles
+    pub manifest: PathBuf,
+
+    /// Seed base: o puzzle N usa seed + N quando não tiver seed própria (padrão: aleatória)
+    #[arg(long)]
+    pub seed: Option<u64>,
+
+    /// Gabaritos por página no fim do caderno
+    #[arg(long, default_value_t = 4, value_parser = parse_keys_per_page)]
+    pub keys_per_page: usize,
+
+    /// Diretório de saída do caderno e dos puzzles em JSON (padrão: pdf/{título})
+    #[arg(short, long)]
+    pub out: Option<PathBuf>,
+
+    /// Não falha quando alguma palavra não puder ser posicionada
+    #[arg(long)]
+    pub allow_discarded: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct OutputArgs {
+    /// Tamanho da página: a4, a5, carta (letter), oficio (legal) ou largura x altura em mm (200x250)
+    #[arg(long, default_value = "a4", value_parser = parse_page_size)]
+    pub page: PageSize,
+
+    /// Orientação da página
+    #[arg(long, value_enum, default_value_t = OrientationArg::Retrato)]
+    pub orientation: OrientationArg,
+
+    /// Formatos de saída separados por vírgula: pdf, svg, png, html
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "pdf")]
+    pub format: Vec<FormatArg>,
+
+    /// Resolução das imagens PNG, em pontos por polegada
+    #[arg(long, default_value_t = 150, value_parser = clap::value_parser!(u32).range(36..=1200))]
+    pub dpi: u32,
+
+    /// Fonte TrueType/OpenType (padrão: DejaVu Sans Mono embutida)
+    #[arg(long)]
+    pub font: Option<PathBuf>,
+
+    /// Contornos do gabarito: coloridos ou em tons de cinza para impressoras preto e branco
+    #[arg(long, value_enum, default_value_t = AnswerStyleArg::Cores)]
+    pub answer_style: AnswerStyleArg,
+
+    /// Gera também uma folha de dicas com a posição da primeira letra de cada palavra
+    #[arg(long)]
+    pub hint_sheet: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct SolveArgs {
+    /// Grid em texto (uma linha de letras por linha) ou arquivo de puzzle (.json)
+    #[arg(short, long)]
+    pub grid: PathBuf,
+
+    /// Palavras separadas por vírgula (padrão: as palavras do arquivo de puzzle)
+    #[arg(short, long, value_delimiter = ',')]
+    pub words: Vec<String>,
+
+    /// Arquivo de palavras: TXT (uma por linha ou por vírgula), CSV (palavra,dica,categoria) ou JSON
+    #[arg(long)]
+    pub words_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DifficultyLevel {
+    #[value(alias = "easy")]
+    Facil,
+    #[value(alias = "medium")]
+    Medio,
+    #[value(alias = "hard")]
+    Dificil,
+}
+
+impl DifficultyLevel {
+    pub fn to_difficulty(self) -> Difficulty {
+        match self {
+            DifficultyLevel::Facil => Difficulty::easy(),
+            DifficultyLevel::Medio => Difficulty::medium(),
+            DifficultyLevel::Dificil => Difficulty::hard(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StrategyArg {
+    #[value(alias = "random")]
+    Aleatoria,
+    Backtracking,
+}
+
+impl StrategyArg {
+    pub fn to_strategy(self) -> PlacementStrategy {
+        match self {
+            StrategyArg::Aleatoria => PlacementStrategy::Random,
+            StrategyArg::Backtracking => PlacementStrategy::Backtracking,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FillerStrategyArg {
+    #[value(alias = "frequency")]
+    Frequencia,
+    #[value(aliases = ["bigramas", "bigram"])]
+    Markov,
+}
+
+impl FillerStrategyArg {
+    pub fn to_strategy(self) -> FillerStrategy {
+        match self {
+            FillerStrategyArg::Frequencia => FillerStrategy::Frequency,
+            FillerStrategyArg::Markov => FillerStrategy::Markov,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ShapeArg {
+    #[value(alias = "circle")]
+    Circulo,
+    #[value(aliases = ["slice", "triangulo", "triangle"])]
+    Fatia,
+    #[value(alias = "diamond")]
+    Losango,
+    #[value(alias = "heart")]
+    Coracao,
+}
+
+impl ShapeArg {
+    pub fn to_shape(self) -> Shape {
+        match self {
+            ShapeArg::Circulo => Shape::Circle,
+            ShapeArg::Fatia => Shape::Slice,
+            ShapeArg::Losango => Shape::Diamond,
+            ShapeArg::Coracao => Shape::Heart,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OrientationArg {
+    #[value(alias = "portrait")]
+    Retrato,
+    #[value(alias = "landscape")]
+    Paisagem,
+}
+
+impl OrientationArg {
+    pub fn to_orientation(self) -> Orientation {
+        match self {
+            OrientationArg::Retrato => Orientation::Portrait,
+            OrientationArg::Paisagem => Orientation::Landscape,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum FormatArg {
+    Pdf,
+    Svg,
+    Png,
+    Html,
+}
+
+impl FormatArg {
+    pub fn to_format(self) -> OutputFormat {
+        match self {
+            FormatArg::Pdf => OutputFormat::Pdf,
+            FormatArg::Svg => OutputFormat::Svg,
+            FormatArg::Png => OutputFormat::Png,
+            FormatArg::Html => OutputFormat::Html,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum AnswerStyleArg {
+    #[value(alias = "color")]
+    Cores,
+    #[value(aliases = ["grayscale", "cinzas"])]
+    Cinza,
+}
+
+impl AnswerStyleArg {
+    pub fn to_answer_style(self) -> AnswerStyle {
+        match self {
+            AnswerStyleArg::Cores => AnswerStyle::Color,
+            AnswerStyleArg::Cinza => AnswerStyle::Grayscale,
+        }
+    }
+}
+
+fn parse_page_size(value: &str) -> Result<PageSize, String> {
+    match value.trim().to_lowercase().as_str() {
+        "a4" => return Ok(PageSize::A4),
+        "a5" => return Ok(PageSize::A5),
+        "carta" | "letter" => return Ok(PageSize::Letter),
+        "oficio" | "ofício" | "legal" => return Ok(PageSize::Legal),
+        _ => {}
+    }
+
+    let parse = |part: &str| -> Result<f32, String> {
+        match part.trim().parse::<f32>() {
+            Ok(mm) if mm >= MIN_PAGE_SIDE_MM => Ok(mm),
+            Ok(_) => Err(format!("as dimensões da página devem ter pelo menos {} mm", MIN_PAGE_SIDE_MM)),
+            Err(_) => Err(format!("dimensão de página inválida: '{}'", part.trim())),
+        }
+    };
+
+    match value.split_once(['x', 'X']) {
+        Some((width, height)) => Ok(PageSize::Custom {
+            width_mm: parse(width)?,
+            height_mm: parse(height)?,
+        }),
+        None => Err(format!("tamanho de página desconhecido: '{}' (use a4, a5, carta, oficio ou LxA em mm)", value)),
+    }
+}
+
+fn parse_filler(value: &str) -> Result<FillerSource, String> {
+    let language = match value.trim().to_lowercase().as_str() {
+        "pt" | "pt-br" | "portugues" | "português" => Language::Portuguese,
+        "en" | "ingles" | "inglês" | "english" => Language::English,
+        "es" | "espanhol" | "spanish" => Language::Spanish,
+        "it" | "italiano" | "italian" => Language::Italian,
+        "fr" | "frances" | "francês" | "french" => Language::French,
+        "palavras" | "words" => return Ok(FillerSource::WordList),
+        _ => {
+            let path = Path::new(value);
+            if !path.exists() {
+                return Err(format!("idioma desconhecido e arquivo não encontrado: '{}' (use pt, en, es, it, fr ou palavras)", value));
+            }
+            return FillerSource::load_table(path).map_err(|e| format!("{}: {}", path.display(), e));
+        }
+    };
+    Ok(FillerSource::Language(language))
+}
+
+/// Um dos níveis prontos ou o caminho de um perfil em JSON.
+pub fn parse_difficulty(value: &str) -> Result<Difficulty, String> {
+    if let Ok(level) = DifficultyLevel::from_str(value.trim(), true) {
+        return Ok(level.to_difficulty());
+    }
+    let path = Path::new(value);
+    if !path.exists() {
+        return Err(format!("dificuldade desconhecida e arquivo não encontrado: '{}' (use facil, medio, dificil ou um perfil .json)", value));
+    }
+    Difficulty::load(path).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+fn parse_keys_per_page(value: &str) -> Result<usize, String> {
+    match value.trim().parse::<usize>() {
+        Ok(n @ (1 | 2 | 4 | 6 | 9)) => Ok(n),
+        _ => Err(format!("valor inválido: '{}' (use 1, 2, 4, 6 ou 9)", value.trim())),
+    }
+}
+
+pub fn parse_grid_size(value: &str) -> Result<(usize, usize), String> {
+    let parse = |part: &str| -> Result<usize, String> {
+        match part.trim().parse::<usize>() {
+            Ok(0) => Err("as dimensões devem ser maiores que zero".to_string()),
+            Ok(n) => Ok(n),
+            Err(_) => Err(format!("dimensão inválida: '{}'", part.trim())),
+        }
+    };
+
+    match value.split_once(['x', 'X']) {
+        Some((width, height)) => Ok((parse(width)?, parse(height)?)),
+        None => {
+            let side = parse(value)?;
+            Ok((side, side))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn command_definition_is_consistent() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn parses_generate_flags() {
+        let argv = ["pizza-wordsearch", "generate", "-t", "Sabores", "-d", "dificil", "-w", "pizza,queijo", "--seed", "42"];
+        let cli = Cli::try_parse_from(argv).unwrap();
+        let Some(Command::Generate(args)) = cli.command else {
+            panic!("esperado o subcomando generate");
+        };
+        assert_eq!(args.title, "Sabores");
+        assert_eq!(args.seed, Some(42));
+        assert_eq!(args.difficulty.name, Difficulty::hard().name);
+    }
+
+    #[test]
+    fn without_a_subcommand_falls_back_to_the_wizard() {
+        assert!(Cli::try_parse_from(["pizza-wordsearch"]).unwrap().command.is_none());
+        assert!(Cli::try_parse_from(["pizza-wordsearch", "generate", "-w", "pizza"]).is_err());
+    }
+
+    #[test]
+    fn grid_sizes_accept_a_side_or_width_by_height() {
+        assert_eq!(parse_grid_size("15"), Ok((15, 15)));
+        assert_eq!(parse_grid_size("20x10"), Ok((20, 10)));
+        assert_eq!(parse_grid_size(" 8 X 12 "), Ok((8, 12)));
+        assert!(parse_grid_size("0x5").is_err());
+        assert!(parse_grid_size("dez").is_err());
+    }
+
+    #[test]
+    fn page_sizes_must_fit_the_layout() {
+        assert!(parse_page_size("80x80").is_err());
+        assert!(parse_page_size("131x80").is_err());
+        assert_eq!(
+            parse_page_size("131x200"),
+            Ok(PageSize::Custom { width_mm: 131.0, height_mm: 200.0 })
+        );
+        assert_eq!(parse_page_size("A5"), Ok(PageSize::A5));
+    }
+}
@@ -0,0 +1,72 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::word::Word;
+
+const MIN_TARGET_LEN: usize = 4;
+const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Palavra quase certa plantada no grid para confundir: a palavra de onde veio e as células.
+#[derive(Debug, Clone)]
+pub struct Decoy {
+    pub text: String,
+    pub target: Word,
+    pub cells: Vec<(usize, usize)>,
+}
+
+/// Variação de uma palavra: sem as últimas letras ("QUEIJ"), sem uma letra do meio ("PIZA")
+/// ou com uma letra trocada ("MOLNO"). Palavras curtas demais não geram iscas.
+pub fn near_miss<R: Rng>(word: &Word, rng: &mut R) -> Option<String> {
+    let letters: Vec<u8> = word.normalized.bytes().collect();
+    let len = letters.len();
+    if len < MIN_TARGET_LEN {
+        return None;
+    }
+
+    let variant = match rng.gen_range(0..3) {
+        0 => {
+            let cut = if len >= 6 { rng.gen_range(1..=2) } else { 1 };
+            letters[..len - cut].to_vec()
+        }
+        1 => {
+            let mut variant = letters.clone();
+            variant.remove(rng.gen_range(1..len - 1));
+            variant
+        }
+        _ => {
+            let mut variant = letters.clone();
+            let index = rng.gen_range(1..len);
+            let options: Vec<u8> = ALPHABET.iter().copied().filter(|&b| b != letters[index]).collect();
+            variant[index] = *options.choose(rng)?;
+            variant
+        }
+    };
+    String::from_utf8(variant).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::seeded_rng;
+
+    #[test]
+    fn near_misses_stay_close_to_the_word_without_matching_it() {
+        let mut rng = seeded_rng(8);
+        for word in ["pizza", "queijo", "oregano", "alho"].map(Word::new) {
+            for _ in 0..50 {
+                let variant = near_miss(&word, &mut rng).unwrap();
+                let len = word.normalized.len();
+                assert_ne!(variant, word.normalized);
+                assert!((len.saturating_sub(2)..=len).contains(&variant.len()), "{} -> {}", word.normalized, variant);
+                assert!(variant.bytes().all(|b| b.is_ascii_uppercase()));
+                assert_eq!(variant.as_bytes()[0], word.normalized.as_bytes()[0], "{} -> {}", word.normalized, variant);
+            }
+        }
+    }
+
+    #[test]
+    fn short_words_get_no_decoys() {
+        let mut rng = seeded_rng(8);
+        assert!(["sal", "ovo", "a"].iter().all(|w| near_miss(&Word::new(w), &mut rng).is_none()));
+    }
+}
@@ -1,67 +1,340 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
 use crate::direction::Direction;
+use crate::filler::FillerStrategy;
+use crate::grid::Slot;
+
+const PRESET_EASY: &str = include_str!("../assets/difficulty/facil.json");
+const PRESET_MEDIUM: &str = include_str!("../assets/difficulty/medio.json");
+const PRESET_HARD: &str = include_str!("../assets/difficulty/dificil.json");
+/// Peso máximo de uma direção; os pesos só valem em proporção uns aos outros.
+pub const MAX_DIRECTION_WEIGHT: u32 = 1000;
 
-#[derive(Debug, Clone)]
+/// Perfil de dificuldade: direções com pesos de sorteio, faixa de letras compartilhadas,
+/// preenchimento e iscas. Os níveis fácil, médio e difícil são perfis no mesmo formato.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "DifficultyFile")]
 pub struct Difficulty {
-    pub allow_horizontal: bool,
-    pub allow_vertical: bool,
-    pub allow_diagonal: bool,
-    pub allow_reverse: bool,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub directions: Vec<DirectionWeight>,
+    /// Proporção de letras compartilhadas entre palavras buscada pelo gerador (0.0 a 1.0).
+    pub min_overlap: f32,
+    pub max_overlap: f32,
+    pub filler_strategy: FillerStrategy,
+    /// Iscas (palavras quase certas) plantadas por palavra posicionada; 0 desativa.
+    pub decoy_density: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct DirectionWeight {
+    pub direction: Direction,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+/// Formato aceito na leitura: o perfil atual ou os quatro booleanos dos arquivos de puzzle
+/// da versão 1, convertidos para as mesmas direções com peso 1.
+#[derive(Deserialize)]
+struct DifficultyFile {
+    #[serde(default)]
+    name: String,
+    directions: Option<Vec<DirectionWeight>>,
+    #[serde(default)]
+    min_overlap: f32,
+    #[serde(default = "default_max_overlap")]
+    max_overlap: f32,
+    #[serde(default)]
+    filler_strategy: FillerStrategy,
+    #[serde(default)]
+    decoy_density: f32,
+    allow_horizontal: Option<bool>,
+    allow_vertical: Option<bool>,
+    allow_diagonal: Option<bool>,
+    allow_reverse: Option<bool>,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+fn default_max_overlap() -> f32 {
+    1.0
+}
+
+impl TryFrom<DifficultyFile> for Difficulty {
+    type Error = String;
+
+    fn try_from(file: DifficultyFile) -> Result<Self, Self::Error> {
+        let directions = match file.directions {
+            Some(directions) => directions,
+            None if file.allow_horizontal.is_some() => legacy_directions(
+                file.allow_horizontal.unwrap_or(false),
+                file.allow_vertical.unwrap_or(false),
+                file.allow_diagonal.unwrap_or(false),
+                file.allow_reverse.unwrap_or(false),
+            ),
+            None => return Err("o perfil precisa da lista \"directions\"".to_string()),
+        };
+
+        let difficulty = Self {
+            name: file.name,
+            directions,
+            min_overlap: file.min_overlap,
+            max_overlap: file.max_overlap,
+            filler_strategy: file.filler_strategy,
+            decoy_density: file.decoy_density,
+        };
+        difficulty.validate()?;
+        Ok(difficulty)
+    }
 }
 
 impl Difficulty {
     pub fn easy() -> Self {
-        Self {
-            allow_horizontal: true,
-            allow_vertical: true,
-            allow_diagonal: false,
-            allow_reverse: false,
-        }
+        Self::preset(PRESET_EASY)
     }
 
     pub fn medium() -> Self {
-        Self {
-            allow_horizontal: true,
-            allow_vertical: true,
-            allow_diagonal: true,
-            allow_reverse: false,
-        }
+        Self::preset(PRESET_MEDIUM)
     }
 
     pub fn hard() -> Self {
-        Self {
-            allow_horizontal: true,
-            allow_vertical: true,
-            allow_diagonal: true,
-            allow_reverse: true,
+        Self::preset(PRESET_HARD)
+    }
+
+    fn preset(json: &str) -> Self {
+        serde_json::from_str(json).expect("perfis embutidos são válidos")
+    }
+
+    /// Lê um perfil em JSON, no mesmo formato dos perfis embutidos em `assets/difficulty/`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        serde_json::from_str(content.trim_start_matches('\u{feff}'))
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.allowed_directions().is_empty() {
+            return Err("o perfil precisa de pelo menos uma direção com peso maior que zero".to_string());
+        }
+        for (i, entry) in self.directions.iter().enumerate() {
+            if entry.weight > MAX_DIRECTION_WEIGHT {
+                return Err(format!(
+                    "o peso da direção {:?} passa do máximo de {}",
+                    entry.direction, MAX_DIRECTION_WEIGHT
+                ));
+            }
+            if self.directions[..i].iter().any(|e| e.direction == entry.direction) {
+                return Err(format!("a direção {:?} aparece mais de uma vez", entry.direction));
+            }
+        }
+        let ratio = 0.0..=1.0;
+        if !ratio.contains(&self.min_overlap) || !ratio.contains(&self.max_overlap) || self.min_overlap > self.max_overlap {
+            return Err("min_overlap e max_overlap devem estar entre 0.0 e 1.0, com min_overlap <= max_overlap".to_string());
         }
+        if !ratio.contains(&self.decoy_density) {
+            return Err("decoy_density deve estar entre 0.0 e 1.0".to_string());
+        }
+        Ok(())
     }
 
+    /// Direções com peso maior que zero, na ordem do perfil e sem repetições.
     pub fn allowed_directions(&self) -> Vec<Direction> {
         let mut directions = Vec::new();
-
-        if self.allow_horizontal {
-            directions.push(Direction::Horizontal);
-            if self.allow_reverse {
-                directions.push(Direction::HorizontalReverse);
+        for entry in &self.directions {
+            if entry.weight > 0 && !directions.contains(&entry.direction) {
+                directions.push(entry.direction);
             }
         }
+        directions
+    }
 
-        if self.allow_vertical {
-            directions.push(Direction::Vertical);
-            if self.allow_reverse {
-                directions.push(Direction::VerticalReverse);
-            }
+    pub fn direction_weight(&self, direction: Direction) -> u32 {
+        self.directions
+            .iter()
+            .filter(|entry| entry.direction == direction)
+            .map(|entry| entry.weight)
+            .sum()
+    }
+
+    pub fn pick_direction<R: Rng>(&self, rng: &mut R) -> Option<Direction> {
+        self.directions
+            .choose_weighted(rng, |entry| entry.weight)
+            .ok()
+            .map(|entry| entry.direction)
+    }
+
+    /// Embaralha as posições candidatas de modo que as direções de maior peso tendam a vir
+    /// primeiro (cada posição recebe a chave `u^(1/peso)`).
+    pub fn shuffle_slots<R: Rng>(&self, slots: &mut [Slot], rng: &mut R) {
+        let mut keyed: Vec<(f64, Slot)> = slots
+            .iter()
+            .map(|&slot| {
+                let weight = self.direction_weight(slot.direction).max(1) as f64;
+                (rng.gen::<f64>().powf(1.0 / weight), slot)
+            })
+            .collect();
+        keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+        for (target, (_, slot)) in slots.iter_mut().zip(keyed) {
+            *target = slot;
         }
+    }
 
-        if self.allow_diagonal {
-            directions.push(Direction::DiagonalDown);
-            directions.push(Direction::DiagonalUp);
-            if self.allow_reverse {
-                directions.push(Direction::DiagonalDownReverse);
-                directions.push(Direction::DiagonalUpReverse);
-            }
+    /// Faixa de letras compartilhadas a buscar, ou `None` quando qualquer proporção serve.
+    pub fn overlap_range(&self) -> Option<(f32, f32)> {
+        (self.min_overlap > 0.0 || self.max_overlap < 1.0).then_some((self.min_overlap, self.max_overlap))
+    }
+
+    /// Troca as direções por uma lista como `Horizontal:3,Vertical,DiagonalUpReverse:2`
+    /// (peso 1 quando omitido).
+    pub fn set_directions(&mut self, spec: &str) -> Result<(), String> {
+        let mut directions = Vec::new();
+        for item in spec.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let (name, weight) = match item.split_once(':') {
+                Some((name, weight)) => {
+                    let weight = weight
+                        .trim()
+                        .parse()
+                        .map_err(|_| format!("peso inválido em '{}'", item))?;
+                    (name.trim(), weight)
+                }
+                None => (item, 1),
+            };
+            let direction = Direction::from_name(name).ok_or_else(|| format!("direção desconhecida: '{}'", name))?;
+            directions.push(DirectionWeight { direction, weight });
         }
+        let previous = std::mem::replace(&mut self.directions, directions);
+        if let Err(e) = self.validate() {
+            self.directions = previous;
+            return Err(e);
+        }
+        Ok(())
+    }
+}
 
-        directions
+fn legacy_directions(horizontal: bool, vertical: bool, diagonal: bool, reverse: bool) -> Vec<DirectionWeight> {
+    let mut directions = Vec::new();
+
+    if horizontal {
+        directions.push(Direction::Horizontal);
+        if reverse {
+            directions.push(Direction::HorizontalReverse);
+        }
+    }
+
+    if vertical {
+        directions.push(Direction::Vertical);
+        if reverse {
+            directions.push(Direction::VerticalReverse);
+        }
+    }
+
+    if diagonal {
+        directions.push(Direction::DiagonalDown);
+        directions.push(Direction::DiagonalUp);
+        if reverse {
+            directions.push(Direction::DiagonalDownReverse);
+            directions.push(Direction::DiagonalUpReverse);
+        }
+    }
+
+    directions
+        .into_iter()
+        .map(|direction| DirectionWeight { direction, weight: 1 })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_weights_above_the_maximum() {
+        let mut difficulty = Difficulty::medium();
+        assert!(difficulty.set_directions("Horizontal:4000000000,Vertical:1000000000").is_err());
+        assert!(difficulty.set_directions("Horizontal:1001").is_err());
+        assert!(difficulty.set_directions("Horizontal:1000,Vertical:1").is_ok());
+
+        let json = r#"{"directions": [{"direction": "Horizontal", "weight": 30000000}]}"#;
+        assert!(serde_json::from_str::<Difficulty>(json).is_err());
+    }
+
+    #[test]
+    fn rejects_repeated_directions() {
+        let mut difficulty = Difficulty::medium();
+        assert!(difficulty.set_directions("Horizontal:1000,Horizontal:1000").is_err());
+    }
+
+    #[test]
+    fn presets_are_valid_and_grow_harder() {
+        let presets = [Difficulty::easy(), Difficulty::medium(), Difficulty::hard()];
+        for preset in &presets {
+            assert!(preset.validate().is_ok(), "{}", preset.name);
+        }
+        let counts: Vec<usize> = presets.iter().map(|p| p.allowed_directions().len()).collect();
+        assert!(counts.windows(2).all(|pair| pair[0] <= pair[1]), "{:?}", counts);
+        assert_eq!(counts[2], Direction::ALL.len());
+    }
+
+    #[test]
+    fn reads_the_legacy_boolean_format() {
+        let json = r#"{"allow_horizontal": true, "allow_vertical": true, "allow_diagonal": false, "allow_reverse": true}"#;
+        let difficulty: Difficulty = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            difficulty.allowed_directions(),
+            vec![Direction::Horizontal, Direction::HorizontalReverse, Direction::Vertical, Direction::VerticalReverse]
+        );
+        assert!(difficulty.directions.iter().all(|entry| entry.weight == 1));
+        assert_eq!((difficulty.min_overlap, difficulty.max_overlap), (0.0, 1.0));
+
+        let missing = serde_json::from_str::<Difficulty>(r#"{"min_overlap": 0.2}"#).unwrap_err();
+        assert!(missing.to_string().contains("directions"));
+        let nothing_allowed = r#"{"allow_horizontal": false, "allow_vertical": false, "allow_diagonal": false, "allow_reverse": true}"#;
+        assert!(serde_json::from_str::<Difficulty>(nothing_allowed).is_err());
+    }
+
+    #[test]
+    fn loads_profiles_from_files() {
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-perfil-{}.json", std::process::id()));
+        let profile = r#"{"name": "Escola", "directions": [{"direction": "Vertical"}], "decoy_density": 0.5}"#;
+        fs::write(&path, format!("\u{feff}{}", profile)).unwrap();
+        let loaded = Difficulty::load(&path);
+        fs::write(&path, r#"{"directions": [{"direction": "Vertical"}], "min_overlap": 0.8, "max_overlap": 0.2}"#).unwrap();
+        let inverted = Difficulty::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!((loaded.name.as_str(), loaded.decoy_density), ("Escola", 0.5));
+        assert_eq!(loaded.allowed_directions(), vec![Direction::Vertical]);
+        assert_eq!(inverted.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn set_directions_parses_weights_and_keeps_the_old_list_on_error() {
+        let mut difficulty = Difficulty::easy();
+        difficulty.set_directions("Horizontal:3, Vertical,DiagonalUpReverse:0").unwrap();
+        assert_eq!(difficulty.allowed_directions(), vec![Direction::Horizontal, Direction::Vertical]);
+        assert_eq!(difficulty.direction_weight(Direction::Horizontal), 3);
+        assert_eq!(difficulty.direction_weight(Direction::Vertical), 1);
+
+        for spec in ["Horizontal:x", "Sideways", "Horizontal:0", ""] {
+            assert!(difficulty.set_directions(spec).is_err(), "{}", spec);
+            assert_eq!(difficulty.direction_weight(Direction::Horizontal), 3, "{}", spec);
+        }
+    }
+
+    #[test]
+    fn heavier_directions_are_picked_more_often() {
+        let mut difficulty = Difficulty::easy();
+        difficulty.set_directions("Horizontal:9,Vertical:1").unwrap();
+        let mut rng = crate::generator::seeded_rng(6);
+        let horizontal = (0..1000).filter(|_| difficulty.pick_direction(&mut rng) == Some(Direction::Horizontal)).count();
+        assert!((850..=950).contains(&horizontal), "{}", horizontal);
     }
 }
@@ -1,7 +1,6 @@
-use rand::seq::SliceRandom;
-use rand::Rng;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Direction {
     Horizontal,
     HorizontalReverse,
@@ -27,7 +26,25 @@ impl Direction {
         }
     }
 
-    pub fn random_from<R: Rng>(directions: &[Direction], rng: &mut R) -> Option<Direction> {
-        directions.choose(rng).copied()
+    pub const ALL: [Direction; 8] = [
+        Direction::Horizontal,
+        Direction::HorizontalReverse,
+        Direction::Vertical,
+        Direction::VerticalReverse,
+        Direction::DiagonalDown,
+        Direction::DiagonalDownReverse,
+        Direction::DiagonalUp,
+        Direction::DiagonalUpReverse,
+    ];
+
+    pub fn from_deltas(dr: i32, dc: i32) -> Option<Direction> {
+        Self::ALL.into_iter().find(|d| d.deltas() == (dr, dc))
+    }
+
+    /// Direção pelo nome usado nos arquivos (`DiagonalUpReverse`), sem diferenciar maiúsculas,
+    /// hífens ou sublinhados (`diagonal-up-reverse`).
+    pub fn from_name(name: &str) -> Option<Direction> {
+        let wanted: String = name.chars().filter(|c| c.is_ascii_alphanumeric()).collect::<String>().to_lowercase();
+        Self::ALL.into_iter().find(|d| format!("{:?}", d).to_lowercase() == wanted)
     }
 }
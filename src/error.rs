@@ -0,0 +1,51 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum AppError {
+    BadInput(String),
+    Unplaceable(Vec<String>),
+    RenderWrite(String),
+    FileWrite(String),
+    Terminal(String),
+    VerificationFailed(Vec<String>),
+}
+
+impl AppError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AppError::BadInput(_) => 2,
+            AppError::Unplaceable(_) => 3,
+            AppError::RenderWrite(_) | AppError::FileWrite(_) | AppError::Terminal(_) => 4,
+            AppError::VerificationFailed(_) => 5,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::BadInput(msg) => write!(f, "Entrada inválida: {}", msg),
+            AppError::Unplaceable(words) => write!(f, "Palavras não posicionadas: {}", words.join(", ")),
+            AppError::RenderWrite(msg) => write!(f, "Erro ao gerar os arquivos de saída: {}", msg),
+            AppError::FileWrite(msg) => write!(f, "Erro ao salvar arquivo: {}", msg),
+            AppError::Terminal(msg) => write!(f, "Erro no terminal: {}", msg),
+            AppError::VerificationFailed(words) => {
+                write!(f, "Palavras que não aparecem exatamente uma vez: {}", words.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_errors_do_not_assume_pdf() {
+        let error = AppError::RenderWrite("puzzle.svg: sem espaço".to_string());
+        assert_eq!(error.exit_code(), 4);
+        assert_eq!(error.to_string(), "Erro ao gerar os arquivos de saída: puzzle.svg: sem espaço");
+    }
+}
@@ -1,24 +1,246 @@
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
 
-const PORTUGUESE_LETTERS: &str = "AEOSRIDMNTCUVLPGQBFHXJZYWK";
+use crate::word::Word;
+
+const ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+/// Frequência das letras em textos de cada idioma, em centésimos de ponto percentual, de A a Z.
+/// Letras acentuadas foram somadas à letra sem acento, já que o grid só usa A-Z.
+const PORTUGUESE: [u32; 26] = [
+    1650, 104, 441, 499, 1336, 102, 130, 78, 632, 40, 2, 278, 474, 445, 1010, 252, 120, 653, 681, 434, 385, 158, 4,
+    25, 1, 47,
+];
+const ENGLISH: [u32; 26] = [
+    817, 149, 278, 425, 1270, 223, 202, 609, 697, 15, 77, 403, 241, 675, 751, 193, 10, 599, 633, 906, 276, 98, 236,
+    15, 197, 7,
+];
+const SPANISH: [u32; 26] = [
+    1203, 222, 402, 501, 1261, 69, 177, 70, 697, 49, 1, 497, 316, 702, 951, 251, 88, 687, 798, 463, 311, 114, 2, 22,
+    101, 47,
+];
+const ITALIAN: [u32; 26] = [
+    1238, 93, 450, 374, 1206, 115, 164, 64, 1017, 1, 1, 651, 251, 688, 983, 306, 51, 637, 498, 562, 318, 210, 3, 1, 2,
+    118,
+];
+const FRENCH: [u32; 26] = [
+    817, 90, 335, 367, 1672, 107, 87, 74, 758, 61, 7, 546, 297, 710, 582, 252, 136, 669, 795, 724, 643, 184, 5, 43, 13,
+    33,
+];
+
+/// Peso mínimo de cada letra ao derivar a distribuição das palavras do puzzle, para que letras
+/// ausentes da lista ainda apareçam de vez em quando.
+const WORD_LIST_FLOOR: u32 = 1;
+const WORD_LIST_SCALE: u32 = 20;
+
+/// Maior peso somado de uma letra numa tabela própria. Os pesos viram milésimos em `u32`, e
+/// com esse limite nem a soma das 26 letras transborda.
+const MAX_TABLE_WEIGHT: f64 = 100_000.0;
+
+/// Chance de uma célula seguir o modelo de bigramas/trigramas em vez da frequência simples;
+/// o restante mantém variedade suficiente para não repetir sempre os mesmos fragmentos.
+const MARKOV_CHANCE: f64 = 0.85;
+
+/// Vizinhas já preenchidas que servem de contexto, como `(dl, dc)` até a letra anterior:
+/// à esquerda, acima, na diagonal acima à esquerda e na diagonal abaixo à esquerda.
+const MARKOV_CONTEXTS: [(i32, i32); 4] = [(0, -1), (-1, 0), (-1, -1), (1, -1)];
+
+const CORPUS_PORTUGUESE: &str = include_str!("../assets/corpus/pt.txt");
+const CORPUS_ENGLISH: &str = include_str!("../assets/corpus/en.txt");
+const CORPUS_SPANISH: &str = include_str!("../assets/corpus/es.txt");
+const CORPUS_ITALIAN: &str = include_str!("../assets/corpus/it.txt");
+const CORPUS_FRENCH: &str = include_str!("../assets/corpus/fr.txt");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Portuguese,
+    English,
+    Spanish,
+    Italian,
+    French,
+}
+
+/// De onde vêm as letras usadas para preencher as células vazias.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FillerSource {
+    Language(Language),
+    /// Tabela lida de um arquivo com `letra peso` por linha.
+    Table(Vec<(char, u32)>),
+    /// Frequência das letras das próprias palavras do puzzle.
+    WordList,
+}
+
+/// Como cada célula vazia é sorteada.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FillerStrategy {
+    /// Cada letra é sorteada sozinha, pela frequência.
+    #[default]
+    #[serde(alias = "frequencia")]
+    Frequency,
+    /// A letra depende das vizinhas já preenchidas, formando fragmentos parecidos com palavras.
+    Markov,
+}
 
 pub struct Filler {
     weights: Vec<(char, u32)>,
     total_weight: u32,
+    model: Option<MarkovModel>,
 }
 
-impl Filler {
-    pub fn new() -> Self {
-        let weights: Vec<(char, u32)> = PORTUGUESE_LETTERS
+/// Contagens de pares e trios de letras consecutivas nas palavras de treino.
+struct MarkovModel {
+    bigrams: Vec<u32>,
+    trigrams: Vec<u32>,
+}
+
+impl Language {
+    fn corpus(self) -> &'static str {
+        match self {
+            Language::Portuguese => CORPUS_PORTUGUESE,
+            Language::English => CORPUS_ENGLISH,
+            Language::Spanish => CORPUS_SPANISH,
+            Language::Italian => CORPUS_ITALIAN,
+            Language::French => CORPUS_FRENCH,
+        }
+    }
+
+    fn frequencies(self) -> &'static [u32; 26] {
+        match self {
+            Language::Portuguese => &PORTUGUESE,
+            Language::English => &ENGLISH,
+            Language::Spanish => &SPANISH,
+            Language::Italian => &ITALIAN,
+            Language::French => &FRENCH,
+        }
+    }
+}
+
+impl Default for FillerSource {
+    fn default() -> Self {
+        FillerSource::Language(Language::Portuguese)
+    }
+}
+
+impl FillerSource {
+    /// Lê uma tabela de frequências: uma letra e um peso por linha, separados por espaço,
+    /// vírgula, ponto e vírgula ou dois-pontos. Linhas vazias e iniciadas com `#` são ignoradas.
+    pub fn load_table(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+
+        let mut weights = [0f64; 26];
+        for (number, line) in content.trim_start_matches('\u{feff}').lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split(|c: char| c.is_whitespace() || matches!(c, ',' | ';' | ':')).filter(|p| !p.is_empty());
+            let (Some(letter), Some(weight), None) = (parts.next(), parts.next(), parts.next()) else {
+                return Err(invalid(format!("linha {}: esperado \"letra peso\"", number + 1)));
+            };
+            let normalized = Word::new(letter).normalized;
+            let mut chars = normalized.chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
+                return Err(invalid(format!("linha {}: '{}' não é uma letra", number + 1, letter)));
+            };
+            let weight: f64 = weight
+                .replace(',', ".")
+                .parse()
+                .ok()
+                .filter(|w: &f64| w.is_finite() && *w >= 0.0)
+                .ok_or_else(|| invalid(format!("linha {}: peso inválido '{}'", number + 1, weight)))?;
+            weights[(ch as u8 - b'A') as usize] += weight;
+        }
+
+        if let Some((ch, _)) = ALPHABET.chars().zip(weights).find(|&(_, w)| w > MAX_TABLE_WEIGHT) {
+            return Err(invalid(format!("o peso da letra {} passa do máximo de {}", ch, MAX_TABLE_WEIGHT)));
+        }
+        let weights: Vec<(char, u32)> = ALPHABET
             .chars()
-            .enumerate()
-            .map(|(i, c)| {
-                let weight = (PORTUGUESE_LETTERS.len() - i) as u32;
-                (c, weight)
-            })
+            .zip(weights)
+            .map(|(ch, w)| (ch, (w * 1000.0).round() as u32))
+            .filter(|&(_, w)| w > 0)
             .collect();
+        if weights.len() < 2 {
+            return Err(invalid("a tabela precisa de pelo menos duas letras com peso maior que zero".to_string()));
+        }
+        Ok(FillerSource::Table(weights))
+    }
+
+    /// Palavras para treinar o modelo de bigramas: o corpus embutido do idioma ou, para
+    /// tabelas próprias e para a lista de palavras, as palavras do puzzle.
+    pub fn corpus(&self, words: &[Word]) -> Vec<Word> {
+        match self {
+            FillerSource::Language(language) => language
+                .corpus()
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+                .flat_map(str::split_whitespace)
+                .map(Word::new)
+                .filter(|w| !w.is_empty())
+                .collect(),
+            FillerSource::Table(_) | FillerSource::WordList => words.to_vec(),
+        }
+    }
+}
+
+impl Filler {
+    pub fn new() -> Self {
+        Self::from_source(&FillerSource::default(), &[])
+    }
+
+    pub fn from_source(source: &FillerSource, words: &[Word]) -> Self {
+        let weights = match source {
+            FillerSource::Language(language) => ALPHABET.chars().zip(language.frequencies().iter().copied()).collect(),
+            FillerSource::Table(weights) => weights.clone(),
+            FillerSource::WordList => {
+                let mut counts = [0u32; 26];
+                for ch in words.iter().flat_map(|w| w.normalized.chars()) {
+                    counts[(ch as u8 - b'A') as usize] += 1;
+                }
+                ALPHABET
+                    .chars()
+                    .zip(counts)
+                    .map(|(ch, count)| (ch, count * WORD_LIST_SCALE + WORD_LIST_FLOOR))
+                    .collect()
+            }
+        };
+        Self::from_weights(weights)
+    }
+
+    fn from_weights(weights: Vec<(char, u32)>) -> Self {
+        let weights: Vec<(char, u32)> = weights.into_iter().filter(|&(_, w)| w > 0).collect();
         let total_weight = weights.iter().map(|(_, w)| w).sum();
-        Self { weights, total_weight }
+        Self { weights, total_weight, model: None }
+    }
+
+    /// Passa a sortear cada letra a partir das vizinhas, com o modelo treinado em `corpus`.
+    /// Letras que não aparecem na distribuição do preenchimento nunca são escolhidas.
+    pub fn with_markov(mut self, corpus: &[Word]) -> Self {
+        let allowed: Vec<bool> = ALPHABET.chars().map(|ch| self.weights.iter().any(|&(c, _)| c == ch)).collect();
+        let mut model = MarkovModel {
+            bigrams: vec![0; 26 * 26],
+            trigrams: vec![0; 26 * 26 * 26],
+        };
+        for word in corpus {
+            let letters: Vec<usize> = word.normalized.bytes().map(|b| (b - b'A') as usize).collect();
+            for pair in letters.windows(2) {
+                if allowed[pair[1]] {
+                    model.bigrams[pair[0] * 26 + pair[1]] += 1;
+                }
+            }
+            for trio in letters.windows(3) {
+                if allowed[trio[2]] {
+                    model.trigrams[(trio[0] * 26 + trio[1]) * 26 + trio[2]] += 1;
+                }
+            }
+        }
+        self.model = Some(model);
+        self
     }
 
     pub fn pick_letter<R: Rng>(&self, rng: &mut R) -> char {
@@ -34,15 +256,82 @@ impl Filler {
         'A'
     }
 
+    /// Sorteia uma letra diferente de `current`, com os pesos das demais. `None` quando a
+    /// distribuição só tem essa letra.
+    pub fn pick_letter_except<R: Rng>(&self, current: Option<char>, rng: &mut R) -> Option<char> {
+        let total: u32 = self.weights.iter().filter(|&&(c, _)| Some(c) != current).map(|(_, w)| w).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut roll = rng.gen_range(0..total);
+        for &(letter, weight) in self.weights.iter().filter(|&&(c, _)| Some(c) != current) {
+            if roll < weight {
+                return Some(letter);
+            }
+            roll -= weight;
+        }
+        None
+    }
+
     pub fn fill_grid<R: Rng>(&self, grid: &mut crate::grid::Grid, rng: &mut R) {
-        for row in 0..grid.size {
-            for col in 0..grid.size {
-                if grid.cells[row][col].is_none() {
-                    grid.cells[row][col] = Some(self.pick_letter(rng));
+        for row in 0..grid.height {
+            for col in 0..grid.width {
+                if grid.is_active(row, col) && grid.cells[row][col].is_none() {
+                    let letter = match &self.model {
+                        Some(model) => self.pick_in_context(model, grid, row, col, rng),
+                        None => self.pick_letter(rng),
+                    };
+                    grid.cells[row][col] = Some(letter);
                 }
             }
         }
     }
+
+    /// Sorteia uma letra que continue a sequência de uma das vizinhas já preenchidas, usando
+    /// o trigrama quando as duas letras anteriores são conhecidas e o bigrama quando só uma é.
+    fn pick_in_context<R: Rng>(&self, model: &MarkovModel, grid: &crate::grid::Grid, row: usize, col: usize, rng: &mut R) -> char {
+        if !rng.gen_bool(MARKOV_CHANCE) {
+            return self.pick_letter(rng);
+        }
+        let letter_at = |steps: i32, (dr, dc): (i32, i32)| {
+            let r = row as i32 + dr * steps;
+            let c = col as i32 + dc * steps;
+            if r < 0 || c < 0 {
+                return None;
+            }
+            grid.get(r as usize, c as usize)
+                .filter(|ch| ch.is_ascii_uppercase())
+                .map(|ch| (ch as u8 - b'A') as usize)
+        };
+
+        let contexts: Vec<(Option<usize>, usize)> = MARKOV_CONTEXTS
+            .iter()
+            .filter_map(|&delta| letter_at(1, delta).map(|prev| (letter_at(2, delta), prev)))
+            .collect();
+        let Some(&(before, prev)) = contexts.get(rng.gen_range(0..contexts.len().max(1))) else {
+            return self.pick_letter(rng);
+        };
+
+        let trigram = before.map(|b| &model.trigrams[(b * 26 + prev) * 26..(b * 26 + prev + 1) * 26]);
+        let bigram = &model.bigrams[prev * 26..(prev + 1) * 26];
+        let counts = match trigram {
+            Some(counts) if counts.iter().any(|&c| c > 0) => counts,
+            _ => bigram,
+        };
+        let total: u32 = counts.iter().sum();
+        if total == 0 {
+            return self.pick_letter(rng);
+        }
+
+        let mut roll = rng.gen_range(0..total);
+        for (index, &count) in counts.iter().enumerate() {
+            if roll < count {
+                return (b'A' + index as u8) as char;
+            }
+            roll -= count;
+        }
+        self.pick_letter(rng)
+    }
 }
 
 impl Default for Filler {
@@ -50,3 +339,100 @@ impl Default for Filler {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::seeded_rng;
+
+    fn table(name: &str, content: &str) -> io::Result<FillerSource> {
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-tabela-{}-{}.txt", name, std::process::id()));
+        fs::write(&path, content).unwrap();
+        let result = FillerSource::load_table(&path);
+        fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn language_tables_cover_the_whole_alphabet() {
+        for language in [Language::Portuguese, Language::English, Language::Spanish, Language::Italian, Language::French] {
+            let frequencies = language.frequencies();
+            assert!(frequencies.iter().all(|&w| w > 0), "{:?}", language);
+            let total: u32 = frequencies.iter().sum();
+            assert!((9_800..=10_200).contains(&total), "{:?} soma {}", language, total);
+        }
+    }
+
+    #[test]
+    fn reads_tables_with_any_separator_and_folds_accents() {
+        let source = table("ok", "\u{feff}# comentário\nA 10\nb 2.5\n\nÃ,1\nz: 0\n").unwrap();
+        assert_eq!(source, FillerSource::Table(vec![('A', 11_000), ('B', 2_500)]));
+    }
+
+    #[test]
+    fn rejects_bad_tables() {
+        for (name, content, message) in [
+            ("formato", "A 1 2\n", "linha 1"),
+            ("letra", "A 1\nAB 2\n", "'AB' não é uma letra"),
+            ("peso", "A 1\nB -3\n", "peso inválido"),
+            ("uma", "A 5\nB 0\n", "pelo menos duas letras"),
+            ("grande", "A 60000\nA 60000\nB 1\n", "peso da letra A"),
+        ] {
+            let error = table(name, content).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+            assert!(error.to_string().contains(message), "{}: {}", name, error);
+        }
+    }
+
+    #[test]
+    fn word_list_distribution_favors_the_puzzle_letters() {
+        let words = [Word::new("pizza"), Word::new("zaza")];
+        let filler = Filler::from_source(&FillerSource::WordList, &words);
+        let weight = |letter| filler.weights.iter().find(|&&(c, _)| c == letter).map(|&(_, w)| w);
+        assert_eq!(weight('Z'), Some(4 * WORD_LIST_SCALE + WORD_LIST_FLOOR));
+        assert_eq!(weight('Q'), Some(WORD_LIST_FLOOR));
+
+        let mut rng = seeded_rng(1);
+        let sample: String = (0..500).map(|_| filler.pick_letter(&mut rng)).collect();
+        assert!(sample.matches('Z').count() > sample.matches('Q').count() * 10);
+    }
+
+    #[test]
+    fn pick_letter_except_never_repeats_the_current_letter() {
+        let filler = Filler::from_source(&FillerSource::Table(vec![('A', 1), ('B', 1)]), &[]);
+        let mut rng = seeded_rng(2);
+        assert!((0..50).all(|_| filler.pick_letter_except(Some('A'), &mut rng) == Some('B')));
+
+        let single = Filler::from_weights(vec![('A', 5), ('B', 0)]);
+        assert_eq!(single.pick_letter_except(Some('A'), &mut rng), None);
+        assert_eq!(single.pick_letter_except(None, &mut rng), Some('A'));
+    }
+
+    #[test]
+    fn markov_filler_follows_the_corpus_sequences() {
+        let corpus = [Word::new("abcabcabc")];
+        let filler = Filler::from_source(&FillerSource::Table(vec![('A', 1), ('B', 1), ('C', 1), ('D', 1)]), &[])
+            .with_markov(&corpus);
+        let mut grid = crate::grid::Grid::new(40, 1);
+        grid.set(0, 0, 'A');
+        filler.fill_grid(&mut grid, &mut seeded_rng(3));
+
+        let row: Vec<char> = (0..40).filter_map(|col| grid.get(0, col)).collect();
+        let follows = row.windows(2).filter(|pair| matches!((pair[0], pair[1]), ('A', 'B') | ('B', 'C') | ('C', 'A'))).count();
+        assert!(follows >= 30, "{}", row.iter().collect::<String>());
+    }
+
+    #[test]
+    fn markov_filler_only_uses_letters_from_the_distribution() {
+        let corpus = FillerSource::default().corpus(&[]);
+        assert!(corpus.len() > 100 && corpus.iter().all(|w| !w.is_empty()));
+
+        let filler = Filler::from_source(&FillerSource::Table(vec![('E', 1), ('R', 1), ('S', 1)]), &[]).with_markov(&corpus);
+        let mut grid = crate::grid::Grid::new(12, 12);
+        grid.set(0, 0, 'Q');
+        filler.fill_grid(&mut grid, &mut seeded_rng(4));
+        let letters: String = grid.cells.iter().flatten().flatten().skip(1).collect();
+        assert_eq!(letters.len(), 143);
+        assert!(letters.chars().all(|ch| "ERS".contains(ch)), "{}", letters);
+    }
+}
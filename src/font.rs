@@ -0,0 +1,175 @@
+use ab_glyph::{Font, FontRef, OutlineCurve};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::render::FontStyle;
+
+const BUNDLED_REGULAR: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
+const BUNDLED_BOLD: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono-Bold.ttf");
+const FALLBACK_ADVANCE_EM: f32 = 0.6;
+
+#[derive(Debug, Clone)]
+pub struct FontSet {
+    pub regular: Vec<u8>,
+    pub bold: Vec<u8>,
+}
+
+/// Segmento do contorno de um glifo, em "em" com y para cima e origem na linha de base.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathOp {
+    MoveTo(f32, f32),
+    LineTo(f32, f32),
+    QuadTo(f32, f32, f32, f32),
+    CubicTo(f32, f32, f32, f32, f32, f32),
+    Close,
+}
+
+impl FontSet {
+    pub fn bundled() -> Self {
+        Self {
+            regular: BUNDLED_REGULAR.to_vec(),
+            bold: BUNDLED_BOLD.to_vec(),
+        }
+    }
+
+    /// Usa o mesmo arquivo para o texto normal e para os títulos.
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let data = fs::read(path)?;
+        FontRef::try_from_slice(&data).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("fonte inválida: {}", e)))?;
+        Ok(Self {
+            bold: data.clone(),
+            regular: data,
+        })
+    }
+
+    /// Largura do texto em unidades de "em" (multiplique pelo tamanho da fonte).
+    pub fn advance_em(&self, text: &str) -> f32 {
+        let Some(font) = self.face(FontStyle::Regular) else {
+            return text.chars().count() as f32 * FALLBACK_ADVANCE_EM;
+        };
+        let units = font.units_per_em().unwrap_or(1000.0);
+        text.chars()
+            .map(|c| match font.glyph_id(c) {
+                id if id.0 == 0 => FALLBACK_ADVANCE_EM,
+                id => font.h_advance_unscaled(id) / units,
+            })
+            .sum()
+    }
+
+    pub fn missing_glyphs(&self, text: &str) -> Vec<char> {
+        let Some(font) = self.face(FontStyle::Regular) else {
+            return Vec::new();
+        };
+        let mut missing: Vec<char> = text
+            .chars()
+            .filter(|c| !c.is_whitespace() && font.glyph_id(*c).0 == 0)
+            .collect();
+        missing.sort_unstable();
+        missing.dedup();
+        missing
+    }
+
+    /// Contorno de um caractere e seu avanço horizontal, para os formatos que desenham o texto
+    /// como vetores ou pixels.
+    pub fn glyph_outline(&self, style: FontStyle, ch: char) -> Option<(Vec<PathOp>, f32)> {
+        let font = self.face(style)?;
+        let units = font.units_per_em().unwrap_or(1000.0);
+        let id = font.glyph_id(ch);
+        let advance = font.h_advance_unscaled(id) / units;
+        let outline = match font.outline(id) {
+            Some(outline) => outline,
+            None => return Some((Vec::new(), advance)),
+        };
+
+        let scale = |p: ab_glyph::Point| (p.x / units, p.y / units);
+        let mut ops = Vec::new();
+        let mut current: Option<ab_glyph::Point> = None;
+        for curve in &outline.curves {
+            let (start, end) = match *curve {
+                OutlineCurve::Line(a, b) | OutlineCurve::Quad(a, _, b) | OutlineCurve::Cubic(a, _, _, b) => (a, b),
+            };
+            if current != Some(start) {
+                if current.is_some() {
+                    ops.push(PathOp::Close);
+                }
+                let (x, y) = scale(start);
+                ops.push(PathOp::MoveTo(x, y));
+            }
+            ops.push(match *curve {
+                OutlineCurve::Line(_, b) => {
+                    let (x, y) = scale(b);
+                    PathOp::LineTo(x, y)
+                }
+                OutlineCurve::Quad(_, c, b) => {
+                    let ((cx, cy), (x, y)) = (scale(c), scale(b));
+                    PathOp::QuadTo(cx, cy, x, y)
+                }
+                OutlineCurve::Cubic(_, c1, c2, b) => {
+                    let ((x1, y1), (x2, y2), (x, y)) = (scale(c1), scale(c2), scale(b));
+                    PathOp::CubicTo(x1, y1, x2, y2, x, y)
+                }
+            });
+            current = Some(end);
+        }
+        if current.is_some() {
+            ops.push(PathOp::Close);
+        }
+        Some((ops, advance))
+    }
+
+    fn face(&self, style: FontStyle) -> Option<FontRef<'_>> {
+        let data = match style {
+            FontStyle::Regular => &self.regular,
+            FontStyle::Bold => &self.bold,
+        };
+        FontRef::try_from_slice(data).ok()
+    }
+}
+
+impl Default for FontSet {
+    fn default() -> Self {
+        Self::bundled()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_font_covers_portuguese_accents() {
+        let fonts = FontSet::bundled();
+        assert!(fonts.missing_glyphs("Caça-Palavras MAÇÃ, PÃO e ORÉGANO").is_empty());
+        assert_eq!(fonts.missing_glyphs("猫 e 猫"), vec!['猫']);
+    }
+
+    #[test]
+    fn monospace_advance_grows_with_each_character() {
+        let fonts = FontSet::bundled();
+        let one = fonts.advance_em("A");
+        assert!(one > 0.0);
+        assert!((fonts.advance_em("AÇÃ") - 3.0 * one).abs() < 1e-4);
+    }
+
+    #[test]
+    fn outlines_are_closed_paths() {
+        let fonts = FontSet::bundled();
+        let (ops, advance) = fonts.glyph_outline(FontStyle::Bold, 'Ç').unwrap();
+        assert!(matches!(ops.first(), Some(PathOp::MoveTo(..))));
+        assert_eq!(ops.last(), Some(&PathOp::Close));
+        assert!(advance > 0.0);
+
+        let (space, _) = fonts.glyph_outline(FontStyle::Regular, ' ').unwrap();
+        assert!(space.is_empty());
+    }
+
+    #[test]
+    fn rejects_files_that_are_not_fonts() {
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-fonte-{}.ttf", std::process::id()));
+        fs::write(&path, "não é uma fonte").unwrap();
+        let result = FontSet::from_file(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
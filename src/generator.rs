@@ -1,24 +1,82 @@
-use rand::Rng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::HashSet;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
+use crate::backtrack::{Backtracker, SearchStatus};
+use crate::blocklist::{Blocklist, BlocklistReport};
+use crate::decoy::{self, Decoy};
 use crate::difficulty::Difficulty;
 use crate::direction::Direction;
-use crate::filler::Filler;
-use crate::grid::{Grid, PlacementResult};
+use crate::filler::{Filler, FillerSource, FillerStrategy};
+use crate::grid::{count_shared_cells, Grid, PlacementResult};
+use crate::mask::Mask;
+use crate::solver::Solver;
 use crate::word::{sort_by_length_desc, Word};
 
+const MAX_REROLL_ROUNDS: usize = 100;
+const MAX_DECOY_ATTEMPTS: usize = 50;
+const MAX_MESSAGE_ATTEMPTS: usize = 20;
+const MAX_GENERATION_ATTEMPTS: usize = 20;
+
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
+}
+
+pub fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementStrategy {
+    Random,
+    Backtracking,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementOutcome {
+    AllPlaced,
+    Partial,
+    ProvenImpossible,
+    BudgetExhausted,
+}
+
 #[derive(Debug, Clone)]
 pub struct GeneratorConfig {
-    pub grid_size: usize,
+    pub width: usize,
+    pub height: usize,
     pub difficulty: Difficulty,
     pub max_attempts_per_word: usize,
+    pub strategy: PlacementStrategy,
+    pub max_search_steps: usize,
+    pub time_limit: Option<Duration>,
+    pub mask: Option<Mask>,
+    pub filler: FillerSource,
+    pub filler_strategy: FillerStrategy,
+    pub blocklist: Blocklist,
+    pub message: Option<String>,
 }
 
 impl GeneratorConfig {
-    pub fn new(grid_size: usize, difficulty: Difficulty) -> Self {
+    pub fn new(width: usize, height: usize, difficulty: Difficulty) -> Self {
+        let filler_strategy = difficulty.filler_strategy;
         Self {
-            grid_size,
+            width,
+            height,
             difficulty,
             max_attempts_per_word: 100,
+            strategy: PlacementStrategy::Random,
+            max_search_steps: 200_000,
+            time_limit: None,
+            mask: None,
+            filler: FillerSource::default(),
+            filler_strategy,
+            blocklist: Blocklist::bundled(),
+            message: None,
         }
     }
 
@@ -26,6 +84,48 @@ impl GeneratorConfig {
         self.max_attempts_per_word = attempts;
         self
     }
+
+    pub fn with_strategy(mut self, strategy: PlacementStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn with_mask(mut self, mask: Mask) -> Self {
+        self.width = mask.width;
+        self.height = mask.height;
+        self.mask = Some(mask);
+        self
+    }
+
+    pub fn with_filler(mut self, filler: FillerSource, strategy: FillerStrategy) -> Self {
+        self.filler = filler;
+        self.filler_strategy = strategy;
+        self
+    }
+
+    pub fn with_blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.blocklist = blocklist;
+        self
+    }
+
+    /// Mensagem secreta escrita, em ordem de leitura, nas células que sobram depois de
+    /// posicionar as palavras, no lugar das letras de preenchimento.
+    pub fn with_message(mut self, message: &str) -> Self {
+        self.message = Some(message.to_string());
+        self
+    }
+
+    pub fn with_search_budget(mut self, max_steps: usize, time_limit: Option<Duration>) -> Self {
+        self.max_search_steps = max_steps;
+        self.time_limit = time_limit;
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WordOccurrences {
+    pub word: Word,
+    pub count: usize,
 }
 
 #[derive(Debug)]
@@ -33,6 +133,19 @@ pub struct GenerationResult {
     pub grid: Grid,
     pub placed_words: Vec<PlacementResult>,
     pub discarded_words: Vec<Word>,
+    pub outcome: PlacementOutcome,
+    pub shared_cells: usize,
+    pub occurrences: Vec<WordOccurrences>,
+    pub decoys: Vec<Decoy>,
+    pub blocked: BlocklistReport,
+    pub message: Option<String>,
+}
+
+impl GenerationResult {
+    /// Palavras que não aparecem exatamente uma vez no grid final.
+    pub fn repeated(&self) -> Vec<&WordOccurrences> {
+        self.occurrences.iter().filter(|o| o.count != 1).collect()
+    }
 }
 
 pub struct Generator {
@@ -45,33 +158,228 @@ impl Generator {
     }
 
     pub fn generate<R: Rng>(&self, words: &[&str], rng: &mut R) -> GenerationResult {
-        let mut grid = Grid::new(self.config.grid_size);
-        let allowed_directions = self.config.difficulty.allowed_directions();
+        let grid = match &self.config.mask {
+            Some(mask) => Grid::with_mask(mask.clone()),
+            None => Grid::new(self.config.width, self.config.height),
+        };
 
-        let mut word_list: Vec<Word> = words
+        let (mut word_list, discarded_words): (Vec<Word>, Vec<Word>) = words
             .iter()
             .map(|w| Word::new(w))
-            .filter(|w| !w.is_empty() && w.len() <= self.config.grid_size)
-            .collect();
+            .filter(|w| !w.is_empty())
+            .partition(|w| w.len() <= grid.longest_line());
         sort_by_length_desc(&mut word_list);
 
-        let mut placed_words = Vec::new();
-        let mut discarded_words = Vec::new();
+        if let Some(message) = &self.config.message {
+            return self.generate_with_message(grid, word_list, discarded_words, message, rng);
+        }
 
-        for word in word_list {
-            match self.try_place_word(&mut grid, &word, &allowed_directions, rng) {
-                Some(result) => placed_words.push(result),
-                None => discarded_words.push(word),
+        // Trocar letras de preenchimento não desfaz uma repetição formada só por letras das
+        // palavras; nesse caso o posicionamento inteiro é refeito, com o mesmo gerador.
+        let mut best: Option<GenerationResult> = None;
+        for _ in 0..MAX_GENERATION_ATTEMPTS {
+            let result = self.place_and_fill(grid.clone(), word_list.clone(), discarded_words.clone(), rng);
+            let rank = |r: &GenerationResult| (r.discarded_words.len(), r.repeated().len());
+            if best.as_ref().is_none_or(|b| rank(&result) < rank(b)) {
+                best = Some(result);
+            }
+            if best.as_ref().is_some_and(|b| b.repeated().is_empty()) {
+                break;
             }
         }
+        best.expect("MAX_GENERATION_ATTEMPTS é maior que zero")
+    }
+
+    fn place_and_fill<R: Rng>(
+        &self,
+        mut grid: Grid,
+        word_list: Vec<Word>,
+        mut discarded_words: Vec<Word>,
+        rng: &mut R,
+    ) -> GenerationResult {
+        let allowed_directions = self.config.difficulty.allowed_directions();
+        let too_long = !discarded_words.is_empty();
+        let mut placed_words = Vec::new();
+
+        let outcome = match self.config.strategy {
+            PlacementStrategy::Random => {
+                for word in word_list {
+                    let placement = match self.config.difficulty.overlap_range() {
+                        Some(range) => self.place_with_overlap(&mut grid, &word, &allowed_directions, &placed_words, range, rng),
+                        None => self.try_place_word(&mut grid, &word, rng),
+                    };
+                    match placement {
+                        Some(result) => placed_words.push(result),
+                        None => discarded_words.push(word),
+                    }
+                }
+                if discarded_words.is_empty() {
+                    PlacementOutcome::AllPlaced
+                } else {
+                    PlacementOutcome::Partial
+                }
+            }
+            PlacementStrategy::Backtracking => {
+                let deadline = self.config.time_limit.map(|limit| Instant::now() + limit);
+                let search = Backtracker::new(&self.config.difficulty, self.config.max_search_steps, deadline).search(grid, &word_list, rng);
+                grid = search.grid;
+                placed_words = search.placed;
+                discarded_words.extend(search.unplaced);
+                match search.status {
+                    _ if too_long => PlacementOutcome::ProvenImpossible,
+                    SearchStatus::Solved => PlacementOutcome::AllPlaced,
+                    SearchStatus::Exhausted => PlacementOutcome::ProvenImpossible,
+                    SearchStatus::OutOfBudget => PlacementOutcome::BudgetExhausted,
+                }
+            }
+        };
 
-        let filler = Filler::new();
+        let shared_cells = count_shared_cells(&placed_words);
+        let decoys = self.place_decoys(&mut grid, &placed_words, rng);
+
+        let placed: Vec<Word> = placed_words.iter().map(|p| p.word.clone()).collect();
+        let filler = match self.config.filler_strategy {
+            FillerStrategy::Frequency => Filler::from_source(&self.config.filler, &placed),
+            FillerStrategy::Markov => Filler::from_source(&self.config.filler, &placed).with_markov(&self.config.filler.corpus(&placed)),
+        };
         filler.fill_grid(&mut grid, rng);
+        let (occurrences, blocked) = self.ensure_unique_occurrences(&mut grid, &placed_words, &decoys, &filler, rng);
 
         GenerationResult {
             grid,
             placed_words,
             discarded_words,
+            outcome,
+            shared_cells,
+            occurrences,
+            decoys,
+            blocked,
+            message: None,
+        }
+    }
+
+    /// Quantas letras as palavras precisam compartilhar para sobrarem exatamente as células da
+    /// mensagem secreta: `letras da mensagem - (células ativas - letras das palavras)`.
+    pub fn required_overlap(&self, words: &[&str], message: &str) -> Result<usize, String> {
+        let grid = match &self.config.mask {
+            Some(mask) => Grid::with_mask(mask.clone()),
+            None => Grid::new(self.config.width, self.config.height),
+        };
+        let letters: usize = words
+            .iter()
+            .map(|w| Word::new(w))
+            .filter(|w| !w.is_empty() && w.len() <= grid.longest_line())
+            .map(|w| w.len())
+            .sum();
+        let message_len = Word::new(message).len();
+        if message_len == 0 {
+            return Err("a mensagem secreta não tem letras".to_string());
+        }
+
+        let free = grid.empty_cells().len() as isize - letters as isize;
+        if free > message_len as isize {
+            return Err(format!(
+                "sobram {} células livres mesmo sem cruzamentos, mas a mensagem tem só {} letras; use uma mensagem mais longa, mais palavras ou um grid menor",
+                free, message_len
+            ));
+        }
+        Ok((message_len as isize - free) as usize)
+    }
+
+    /// Posiciona as palavras com o número exato de cruzamentos e escreve a mensagem nas
+    /// células restantes. Repete a busca se a mensagem formar outra ocorrência de alguma palavra
+    /// ou uma palavra bloqueada, e fica com o arranjo que tiver menos problemas.
+    fn generate_with_message<R: Rng>(
+        &self,
+        empty: Grid,
+        words: Vec<Word>,
+        mut discarded_words: Vec<Word>,
+        message: &str,
+        rng: &mut R,
+    ) -> GenerationResult {
+        let originals: Vec<&str> = words.iter().map(|w| w.original.as_str()).collect();
+        let letters = Word::new(message).chars();
+
+        let mut outcome = PlacementOutcome::ProvenImpossible;
+        // Número de problemas, grid, palavras, ocorrências e palavras bloqueadas do melhor arranjo.
+        type Attempt = (usize, Grid, Vec<PlacementResult>, Vec<WordOccurrences>, BlocklistReport);
+        let mut best: Option<Attempt> = None;
+
+        if let (true, Ok(required)) = (discarded_words.is_empty(), self.required_overlap(&originals, message)) {
+            for _ in 0..MAX_MESSAGE_ATTEMPTS {
+                let deadline = self.config.time_limit.map(|limit| Instant::now() + limit);
+                let search = Backtracker::new(&self.config.difficulty, self.config.max_search_steps, deadline)
+                    .with_exact_overlap(Some(required))
+                    .search(empty.clone(), &words, rng);
+                match search.status {
+                    SearchStatus::Solved => outcome = PlacementOutcome::AllPlaced,
+                    SearchStatus::Exhausted => break,
+                    SearchStatus::OutOfBudget => {
+                        if best.is_none() {
+                            outcome = PlacementOutcome::BudgetExhausted;
+                        }
+                        break;
+                    }
+                }
+
+                let mut grid = search.grid;
+                for ((row, col), letter) in grid.empty_cells().into_iter().zip(letters.iter().copied()) {
+                    grid.set(row, col, letter);
+                }
+                // O backtracking devolve as palavras na ordem da busca, não na da lista.
+                let placed: Vec<Word> = search.placed.iter().map(|p| p.word.clone()).collect();
+                let solver = Solver::new(&placed);
+                let occurrences: Vec<WordOccurrences> = search
+                    .placed
+                    .iter()
+                    .zip(solver.find_grouped(&grid))
+                    .map(|(p, found)| WordOccurrences {
+                        word: p.word.clone(),
+                        count: found.len(),
+                    })
+                    .collect();
+                let blocked = BlocklistReport {
+                    avoided: Vec::new(),
+                    remaining: self.blocked_leftovers(&grid, &search.placed, &[]).0,
+                    decoys: Vec::new(),
+                };
+
+                let problems = occurrences.iter().filter(|o| o.count != 1).count() + blocked.remaining.len();
+                if best.as_ref().is_none_or(|(fewest, ..)| problems < *fewest) {
+                    best = Some((problems, grid, search.placed, occurrences, blocked));
+                }
+                if problems == 0 {
+                    break;
+                }
+            }
+        }
+
+        match best {
+            Some((_, grid, placed_words, occurrences, blocked)) => GenerationResult {
+                grid,
+                shared_cells: count_shared_cells(&placed_words),
+                placed_words,
+                discarded_words,
+                outcome,
+                occurrences,
+                decoys: Vec::new(),
+                blocked,
+                message: Some(message.to_string()),
+            },
+            None => {
+                discarded_words.extend(words);
+                GenerationResult {
+                    grid: empty,
+                    placed_words: Vec::new(),
+                    discarded_words,
+                    outcome,
+                    shared_cells: 0,
+                    occurrences: Vec::new(),
+                    decoys: Vec::new(),
+                    blocked: BlocklistReport::default(),
+                    message: None,
+                }
+            }
         }
     }
 
@@ -79,18 +387,13 @@ impl Generator {
         &self,
         grid: &mut Grid,
         word: &Word,
-        allowed_directions: &[Direction],
         rng: &mut R,
     ) -> Option<PlacementResult> {
-        if allowed_directions.is_empty() {
-            return None;
-        }
-
         for _ in 0..self.config.max_attempts_per_word {
-            let start_row = rng.gen_range(0..self.config.grid_size);
-            let start_col = rng.gen_range(0..self.config.grid_size);
+            let start_row = rng.gen_range(0..self.config.height);
+            let start_col = rng.gen_range(0..self.config.width);
 
-            let direction = Direction::random_from(allowed_directions, rng)?;
+            let direction = self.config.difficulty.pick_direction(rng)?;
 
             if grid.can_place(word, start_row, start_col, direction) {
                 return Some(grid.place_word(word, start_row, start_col, direction));
@@ -99,4 +402,366 @@ impl Generator {
 
         None
     }
+
+    fn place_with_overlap<R: Rng>(
+        &self,
+        grid: &mut Grid,
+        word: &Word,
+        allowed_directions: &[Direction],
+        placed: &[PlacementResult],
+        (min, max): (f32, f32),
+        rng: &mut R,
+    ) -> Option<PlacementResult> {
+        let slots = grid.candidate_slots(word, allowed_directions);
+        let overlaps: Vec<usize> = slots.iter().map(|&slot| grid.overlap_count(word, slot)).collect();
+
+        let ratio = grid.crossing_ratio(placed);
+        let wanted = if ratio < min {
+            overlaps.iter().max().copied()
+        } else if ratio >= max {
+            overlaps.iter().min().copied()
+        } else {
+            None
+        };
+
+        let best: Vec<_> = slots
+            .iter()
+            .zip(&overlaps)
+            .filter(|(_, &overlap)| wanted.is_none_or(|wanted| overlap == wanted))
+            .map(|(slot, _)| *slot)
+            .collect();
+        let slot = best
+            .choose_weighted(rng, |slot| self.config.difficulty.direction_weight(slot.direction) as f64)
+            .ok()?;
+
+        Some(grid.place_word(word, slot.row, slot.col, slot.direction))
+    }
+
+    /// Planta variações quase certas das palavras em células livres. Uma isca só fica se não
+    /// formar nenhuma ocorrência nova de uma palavra-alvo com as letras já presentes no grid.
+    fn place_decoys<R: Rng>(
+        &self,
+        grid: &mut Grid,
+        placed: &[PlacementResult],
+        rng: &mut R,
+    ) -> Vec<Decoy> {
+        let wanted = (placed.len() as f32 * self.config.difficulty.decoy_density).round() as usize;
+        if wanted == 0 {
+            return Vec::new();
+        }
+
+        let words: Vec<Word> = placed.iter().map(|p| p.word.clone()).collect();
+        let solver = Solver::new(&words);
+        let baseline: Vec<usize> = solver.find_grouped(grid).iter().map(Vec::len).collect();
+        let blocked = (!self.config.blocklist.is_empty()).then(|| Solver::new(self.config.blocklist.words()));
+        let blocked_count = |grid: &Grid| blocked.as_ref().map_or(0, |b| b.find_grouped(grid).iter().map(Vec::len).sum::<usize>());
+        let blocked_baseline = blocked_count(grid);
+        let mut decoys = Vec::new();
+
+        for _ in 0..wanted * MAX_DECOY_ATTEMPTS {
+            if decoys.len() == wanted {
+                break;
+            }
+            let (Some(target), Some(direction)) = (words.choose(rng), self.config.difficulty.pick_direction(rng)) else {
+                break;
+            };
+            let Some(text) = decoy::near_miss(target, rng) else {
+                continue;
+            };
+
+            let (dr, dc) = direction.deltas();
+            let (row, col) = (rng.gen_range(0..grid.height) as i32, rng.gen_range(0..grid.width) as i32);
+            let cells: Vec<(usize, usize)> = (0..text.len() as i32)
+                .map(|i| (row + dr * i, col + dc * i))
+                .take_while(|&(r, c)| r >= 0 && c >= 0)
+                .map(|(r, c)| (r as usize, c as usize))
+                .filter(|&(r, c)| grid.is_active(r, c) && grid.get(r, c).is_none())
+                .collect();
+            if cells.len() != text.len() {
+                continue;
+            }
+
+            for (&(r, c), letter) in cells.iter().zip(text.chars()) {
+                grid.set(r, c, letter);
+            }
+            // A isca também não pode formar uma palavra bloqueada, sozinha ou com as letras vizinhas.
+            let counts: Vec<usize> = solver.find_grouped(grid).iter().map(Vec::len).collect();
+            if counts != baseline || blocked_count(grid) != blocked_baseline {
+                for &(r, c) in &cells {
+                    grid.cells[r][c] = None;
+                }
+                continue;
+            }
+            decoys.push(Decoy {
+                text,
+                target: target.clone(),
+                cells,
+            });
+        }
+
+        decoys
+    }
+
+    /// Troca letras de preenchimento até que cada palavra apareça uma única vez e nenhuma
+    /// palavra da lista de bloqueio fique no grid.
+    fn ensure_unique_occurrences<R: Rng>(
+        &self,
+        grid: &mut Grid,
+        placed: &[PlacementResult],
+        decoys: &[Decoy],
+        filler: &Filler,
+        rng: &mut R,
+    ) -> (Vec<WordOccurrences>, BlocklistReport) {
+        let fixed: HashSet<(usize, usize)> = placed
+            .iter()
+            .flat_map(|p| p.positions())
+            .chain(decoys.iter().flat_map(|d| d.cells.iter().copied()))
+            .collect();
+        let words: Vec<Word> = placed.iter().map(|p| p.word.clone()).collect();
+        let solver = Solver::new(&words);
+        let blocked = (!self.config.blocklist.is_empty()).then(|| Solver::new(self.config.blocklist.words()));
+        let mut report = BlocklistReport::default();
+
+        for _ in 0..MAX_REROLL_ROUNDS {
+            let mut rerolled = false;
+
+            for (placement, occurrences) in placed.iter().zip(solver.find_grouped(grid)) {
+                let mut own = placement.positions();
+                own.sort_unstable();
+
+                for occurrence in occurrences {
+                    let mut cells = occurrence.positions();
+                    cells.sort_unstable();
+                    if cells != own && reroll_free_cell(grid, &cells, &fixed, filler, rng) {
+                        rerolled = true;
+                    }
+                }
+            }
+
+            let found: Vec<PlacementResult> = blocked.iter().flat_map(|b| b.find_grouped(grid)).flatten().collect();
+            for occurrence in found {
+                if reroll_free_cell(grid, &occurrence.positions(), &fixed, filler, rng) {
+                    report.avoided.push(occurrence);
+                    rerolled = true;
+                }
+            }
+
+            if !rerolled {
+                break;
+            }
+        }
+
+        (report.remaining, report.decoys) = self.blocked_leftovers(grid, placed, decoys);
+        let occurrences = placed
+            .iter()
+            .zip(solver.find_grouped(grid))
+            .map(|(p, occurrences)| WordOccurrences {
+                word: p.word.clone(),
+                count: occurrences.len(),
+            })
+            .collect();
+        (occurrences, report)
+    }
+
+    /// Palavras bloqueadas que continuam no grid, separando as que passam por alguma isca.
+    /// As que cabem inteiras dentro de uma única palavra do puzzle (PICA em PICANHA) foram
+    /// escolha do autor e não entram em nenhuma das listas.
+    fn blocked_leftovers(&self, grid: &Grid, placed: &[PlacementResult], decoys: &[Decoy]) -> (Vec<PlacementResult>, Vec<PlacementResult>) {
+        if self.config.blocklist.is_empty() {
+            return (Vec::new(), Vec::new());
+        }
+        let words_cells: Vec<HashSet<(usize, usize)>> = placed.iter().map(|p| p.positions().into_iter().collect()).collect();
+        let decoy_cells: HashSet<(usize, usize)> = decoys.iter().flat_map(|d| d.cells.iter().copied()).collect();
+        Solver::new(self.config.blocklist.words())
+            .find_grouped(grid)
+            .into_iter()
+            .flatten()
+            .filter(|o| !words_cells.iter().any(|cells| o.positions().iter().all(|pos| cells.contains(pos))))
+            .partition(|o| !o.positions().iter().any(|pos| decoy_cells.contains(pos)))
+    }
+}
+
+/// Sorteia outra letra para uma das células que não pertencem a palavras nem iscas.
+/// Devolve `false` quando todas as células estão presas ou o preenchimento só tem uma letra.
+fn reroll_free_cell<R: Rng>(
+    grid: &mut Grid,
+    cells: &[(usize, usize)],
+    fixed: &HashSet<(usize, usize)>,
+    filler: &Filler,
+    rng: &mut R,
+) -> bool {
+    let free: Vec<_> = cells.iter().filter(|pos| !fixed.contains(pos)).copied().collect();
+    let Some(&(row, col)) = free.choose(rng) else {
+        return false;
+    };
+    let Some(letter) = filler.pick_letter_except(grid.get(row, col), rng) else {
+        return false;
+    };
+    grid.set(row, col, letter);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WORDS: [&str; 11] = ["pizza", "queijo", "molho", "forno", "massa", "tomate", "cebola", "oregano", "sal", "ovo", "alho"];
+
+    fn snapshot(result: &GenerationResult) -> String {
+        let placements: Vec<(usize, usize, Direction)> =
+            result.placed_words.iter().map(|p| (p.row, p.col, p.direction)).collect();
+        format!("{}{:?}", serde_json::to_string(&result.grid).unwrap(), placements)
+    }
+
+    #[test]
+    fn same_seed_and_config_give_the_same_grid() {
+        let mut markov = Difficulty::hard();
+        markov.filler_strategy = FillerStrategy::Markov;
+        for difficulty in [Difficulty::easy(), Difficulty::hard(), markov] {
+            let generator = Generator::new(GeneratorConfig::new(12, 12, difficulty));
+            let first = generator.generate(&WORDS, &mut seeded_rng(1234));
+            let second = generator.generate(&WORDS, &mut seeded_rng(1234));
+            assert_eq!(snapshot(&first), snapshot(&second));
+            assert_ne!(snapshot(&first), snapshot(&generator.generate(&WORDS, &mut seeded_rng(1235))));
+        }
+    }
+
+    #[test]
+    fn overlap_range_steers_how_many_letters_are_shared() {
+        let config = |min: f32, max: f32| {
+            let mut difficulty = Difficulty::medium();
+            (difficulty.min_overlap, difficulty.max_overlap) = (min, max);
+            Generator::new(GeneratorConfig::new(15, 15, difficulty))
+        };
+        let (apart, crossing) = (config(0.0, 0.0), config(0.5, 1.0));
+        for seed in 0..20 {
+            assert_eq!(apart.generate(&WORDS[..6], &mut seeded_rng(seed)).shared_cells, 0, "seed {}", seed);
+            assert!(crossing.generate(&WORDS[..6], &mut seeded_rng(seed)).shared_cells > 0, "seed {}", seed);
+        }
+    }
+
+    #[test]
+    fn masked_cells_stay_empty() {
+        let mask = Mask::from_shape(crate::mask::Shape::Heart, 14, 14);
+        let generator = Generator::new(GeneratorConfig::new(14, 14, Difficulty::hard()).with_mask(mask.clone()));
+        let result = generator.generate(&WORDS, &mut seeded_rng(5));
+        for row in 0..14 {
+            for col in 0..14 {
+                assert_eq!(result.grid.get(row, col).is_some(), mask.is_active(row, col), "({}, {})", row, col);
+            }
+        }
+        for placement in &result.placed_words {
+            assert!(placement.positions().iter().all(|&(row, col)| mask.is_active(row, col)));
+        }
+    }
+
+    #[test]
+    fn every_word_appears_exactly_once_across_seeds() {
+        let mut markov = Difficulty::hard();
+        markov.filler_strategy = FillerStrategy::Markov;
+        let runs = (0..60).map(|seed| (seed, Difficulty::hard())).chain((0..30).map(|seed| (seed, markov.clone())));
+        for (seed, difficulty) in runs {
+            let generator = Generator::new(GeneratorConfig::new(10, 10, difficulty).with_max_attempts(200));
+            let result = generator.generate(&WORDS, &mut seeded_rng(seed));
+            assert!(result.discarded_words.is_empty(), "seed {}", seed);
+
+            let words: Vec<Word> = result.placed_words.iter().map(|p| p.word.clone()).collect();
+            for (placement, found) in result.placed_words.iter().zip(Solver::new(&words).find_grouped(&result.grid)) {
+                assert_eq!(found.len(), 1, "seed {}: {}", seed, placement.word.original);
+                // Palíndromos são achados a partir da outra ponta; basta ocuparem as mesmas células.
+                let cells = |p: &PlacementResult| {
+                    let mut cells = p.positions();
+                    cells.sort();
+                    cells
+                };
+                assert_eq!(cells(&found[0]), cells(placement), "seed {}: {}", seed, placement.word.original);
+            }
+        }
+    }
+
+    #[test]
+    fn decoys_fill_free_cells_without_adding_occurrences() {
+        let mut difficulty = Difficulty::hard();
+        difficulty.decoy_density = 1.0;
+        let generator = Generator::new(GeneratorConfig::new(14, 14, difficulty).with_max_attempts(200));
+        for seed in 0..10 {
+            let result = generator.generate(&WORDS, &mut seeded_rng(seed));
+            assert!(!result.decoys.is_empty(), "seed {}", seed);
+            assert!(result.repeated().is_empty(), "seed {}", seed);
+
+            let word_cells: HashSet<(usize, usize)> = result.placed_words.iter().flat_map(|p| p.positions()).collect();
+            for decoy in &result.decoys {
+                assert_ne!(decoy.text, decoy.target.normalized);
+                let spelled: String = decoy.cells.iter().filter_map(|&(row, col)| result.grid.get(row, col)).collect();
+                assert_eq!(spelled, decoy.text, "seed {}", seed);
+                assert!(decoy.cells.iter().all(|cell| !word_cells.contains(cell)), "seed {}: {}", seed, decoy.text);
+            }
+        }
+
+        let plain = Generator::new(GeneratorConfig::new(14, 14, Difficulty::easy()));
+        assert!(plain.generate(&WORDS, &mut seeded_rng(0)).decoys.is_empty());
+    }
+
+    #[test]
+    fn filler_never_spells_blocked_words() {
+        let mut blocklist = Blocklist::default();
+        // Sílabas comuns, para que o preenchimento as forme por acaso com frequência.
+        blocklist.extend_from_str("ERA\nSER\nASA\nOSSO\nMOL");
+        let mut difficulty = Difficulty::hard();
+        difficulty.decoy_density = 1.0;
+        let generator = Generator::new(GeneratorConfig::new(12, 12, difficulty).with_blocklist(blocklist.clone()));
+
+        let mut avoided = 0;
+        for seed in 0..10 {
+            let result = generator.generate(&WORDS, &mut seeded_rng(seed));
+            avoided += result.blocked.avoided.len();
+
+            let fixed: HashSet<(usize, usize)> = result
+                .placed_words
+                .iter()
+                .flat_map(|p| p.positions())
+                .chain(result.decoys.iter().flat_map(|d| d.cells.iter().copied()))
+                .collect();
+            let found: Vec<PlacementResult> =
+                Solver::new(blocklist.words()).find_grouped(&result.grid).into_iter().flatten().collect();
+            assert!(found.len() >= result.blocked.remaining.len() + result.blocked.decoys.len(), "seed {}", seed);
+            // "MOL" pode sobrar dentro de MOLHO, mas só com letras de palavras e iscas.
+            for occurrence in &found {
+                assert!(occurrence.positions().iter().all(|cell| fixed.contains(cell)), "seed {}: {:?}", seed, occurrence);
+            }
+        }
+        assert!(avoided > 0);
+    }
+
+    #[test]
+    fn required_overlap_leaves_room_for_the_message() {
+        let generator = Generator::new(GeneratorConfig::new(6, 6, Difficulty::medium()));
+        let words = ["pizza", "forno", "molho", "massa", "sal", "queijo mussarela"];
+        // 36 células, 23 letras que cabem no grid: 13 livres sem cruzamentos.
+        assert_eq!(generator.required_overlap(&words, "Boa pizza pra você"), Ok(2));
+        assert_eq!(generator.required_overlap(&words, "Bom apetite, pizzaiolo"), Ok(6));
+        assert!(generator.required_overlap(&words, "Bom apetite").unwrap_err().contains("sobram 13"));
+        assert!(generator.required_overlap(&words, "123 !").is_err());
+    }
+
+    #[test]
+    fn leftover_cells_spell_the_message_in_reading_order() {
+        let words = ["pizza", "forno", "molho", "massa", "sal"];
+        let generator = Generator::new(GeneratorConfig::new(6, 6, Difficulty::medium()).with_message("Boa pizza pra você"));
+        for seed in 0..10 {
+            let result = generator.generate(&words, &mut seeded_rng(seed));
+            assert!(result.discarded_words.is_empty(), "seed {}", seed);
+            assert!(result.repeated().is_empty(), "seed {}", seed);
+            assert_eq!(result.shared_cells, 2, "seed {}", seed);
+            assert_eq!(result.message.as_deref(), Some("Boa pizza pra você"));
+
+            let used: HashSet<(usize, usize)> = result.placed_words.iter().flat_map(|p| p.positions()).collect();
+            let leftover: String = (0..6)
+                .flat_map(|row| (0..6).map(move |col| (row, col)))
+                .filter(|cell| !used.contains(cell))
+                .filter_map(|(row, col)| result.grid.get(row, col))
+                .collect();
+            assert_eq!(leftover, "BOAPIZZAPRAVOCE", "seed {}", seed);
+        }
+    }
 }
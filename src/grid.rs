@@ -1,13 +1,27 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
 use crate::direction::Direction;
+use crate::mask::Mask;
 use crate::word::Word;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "GridData", try_from = "GridData")]
 pub struct Grid {
     pub cells: Vec<Vec<Option<char>>>,
-    pub size: usize,
+    pub width: usize,
+    pub height: usize,
+    pub mask: Mask,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GridData {
+    width: usize,
+    height: usize,
+    rows: Vec<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlacementResult {
     pub word: Word,
     pub row: usize,
@@ -15,24 +29,172 @@ pub struct PlacementResult {
     pub direction: Direction,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot {
+    pub row: usize,
+    pub col: usize,
+    pub direction: Direction,
+}
+
+impl From<Grid> for GridData {
+    fn from(grid: Grid) -> Self {
+        let rows = grid
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(row, cells)| {
+                cells
+                    .iter()
+                    .enumerate()
+                    .map(|(col, cell)| match cell {
+                        _ if !grid.is_active(row, col) => '.',
+                        Some(ch) => *ch,
+                        None => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            width: grid.width,
+            height: grid.height,
+            rows,
+        }
+    }
+}
+
+impl TryFrom<GridData> for Grid {
+    type Error = String;
+
+    fn try_from(data: GridData) -> Result<Self, Self::Error> {
+        if data.rows.len() != data.height {
+            return Err(format!("esperadas {} linhas no grid, encontradas {}", data.height, data.rows.len()));
+        }
+
+        let mut grid = Grid::new(data.width, data.height);
+        for (row, line) in data.rows.iter().enumerate() {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.len() != data.width {
+                return Err(format!("a linha {} do grid tem {} células, esperado {}", row + 1, chars.len(), data.width));
+            }
+            for (col, ch) in chars.into_iter().enumerate() {
+                match ch {
+                    '.' => grid.mask.set_active(row, col, false),
+                    ' ' => {}
+                    letter @ 'A'..='Z' => grid.set(row, col, letter),
+                    other => {
+                        return Err(format!(
+                            "caractere inválido '{}' na linha {}, coluna {} do grid (use A-Z, espaço ou '.')",
+                            other,
+                            row + 1,
+                            col + 1
+                        ))
+                    }
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+}
+
+impl PlacementResult {
+    pub fn positions(&self) -> Vec<(usize, usize)> {
+        let (dr, dc) = self.direction.deltas();
+        (0..self.word.len())
+            .map(|i| {
+                let row = (self.row as i32 + dr * i as i32) as usize;
+                let col = (self.col as i32 + dc * i as i32) as usize;
+                (row, col)
+            })
+            .collect()
+    }
+}
+
+pub fn count_shared_cells(placements: &[PlacementResult]) -> usize {
+    let mut seen = HashMap::new();
+    for placement in placements {
+        for pos in placement.positions() {
+            *seen.entry(pos).or_insert(0usize) += 1;
+        }
+    }
+    seen.values().filter(|&&count| count > 1).count()
+}
+
 impl Grid {
-    pub fn new(size: usize) -> Self {
-        let cells = vec![vec![None; size]; size];
-        Self { cells, size }
+    pub fn new(width: usize, height: usize) -> Self {
+        Self::with_mask(Mask::full(width, height))
+    }
+
+    pub fn with_mask(mask: Mask) -> Self {
+        let (width, height) = (mask.width, mask.height);
+        let cells = vec![vec![None; width]; height];
+        Self { cells, width, height, mask }
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let rows: Vec<Vec<Option<char>>> = text
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| {
+                line.chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(|c| match c {
+                        '.' | '_' | '*' => None,
+                        letter => Word::new(&letter.to_string()).chars().first().copied(),
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        if width == 0 {
+            return Err("grid vazio".to_string());
+        }
+        if let Some((i, row)) = rows.iter().enumerate().find(|(_, row)| row.len() != width) {
+            return Err(format!("a linha {} tem {} letras, esperado {}", i + 1, row.len(), width));
+        }
+
+        let mut mask = Mask::full(width, height);
+        for (row, cells) in rows.iter().enumerate() {
+            for (col, cell) in cells.iter().enumerate() {
+                mask.set_active(row, col, cell.is_some());
+            }
+        }
+
+        Ok(Self { cells: rows, width, height, mask })
+    }
+
+    pub fn longest_line(&self) -> usize {
+        self.width.max(self.height)
     }
 
     pub fn get(&self, row: usize, col: usize) -> Option<char> {
         self.cells.get(row).and_then(|r| r.get(col).copied().flatten())
     }
 
+    pub fn is_active(&self, row: usize, col: usize) -> bool {
+        self.mask.is_active(row, col)
+    }
+
+    /// Células ativas ainda sem letra, em ordem de leitura (linha a linha, da esquerda para a direita).
+    pub fn empty_cells(&self) -> Vec<(usize, usize)> {
+        (0..self.height)
+            .flat_map(|row| (0..self.width).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.is_active(row, col) && self.get(row, col).is_none())
+            .collect()
+    }
+
     pub fn set(&mut self, row: usize, col: usize, ch: char) {
-        if row < self.size && col < self.size {
+        if self.is_active(row, col) {
             self.cells[row][col] = Some(ch);
         }
     }
 
     fn in_bounds(&self, row: i32, col: i32) -> bool {
-        row >= 0 && col >= 0 && (row as usize) < self.size && (col as usize) < self.size
+        row >= 0 && col >= 0 && (row as usize) < self.height && (col as usize) < self.width
     }
 
     pub fn can_place(&self, word: &Word, start_row: usize, start_col: usize, direction: Direction) -> bool {
@@ -43,7 +205,7 @@ impl Grid {
             let row = start_row as i32 + dr * i as i32;
             let col = start_col as i32 + dc * i as i32;
 
-            if !self.in_bounds(row, col) {
+            if !self.in_bounds(row, col) || !self.is_active(row as usize, col as usize) {
                 return false;
             }
 
@@ -57,6 +219,49 @@ impl Grid {
         true
     }
 
+    pub fn candidate_slots(&self, word: &Word, directions: &[Direction]) -> Vec<Slot> {
+        let mut slots = Vec::new();
+        for &direction in directions {
+            for row in 0..self.height {
+                for col in 0..self.width {
+                    if self.can_place(word, row, col, direction) && self.adds_new_cell(word, row, col, direction) {
+                        slots.push(Slot { row, col, direction });
+                    }
+                }
+            }
+        }
+        slots
+    }
+
+    pub fn overlap_count(&self, word: &Word, slot: Slot) -> usize {
+        let (dr, dc) = slot.direction.deltas();
+        (0..word.len())
+            .filter(|&i| {
+                let row = (slot.row as i32 + dr * i as i32) as usize;
+                let col = (slot.col as i32 + dc * i as i32) as usize;
+                self.get(row, col).is_some()
+            })
+            .count()
+    }
+
+    pub fn crossing_ratio(&self, placements: &[PlacementResult]) -> f32 {
+        let letters: usize = placements.iter().map(|p| p.word.len()).sum();
+        if letters == 0 {
+            return 0.0;
+        }
+        let filled = self.cells.iter().flatten().filter(|c| c.is_some()).count();
+        letters.saturating_sub(filled) as f32 / letters as f32
+    }
+
+    fn adds_new_cell(&self, word: &Word, start_row: usize, start_col: usize, direction: Direction) -> bool {
+        let (dr, dc) = direction.deltas();
+        (0..word.len()).any(|i| {
+            let row = (start_row as i32 + dr * i as i32) as usize;
+            let col = (start_col as i32 + dc * i as i32) as usize;
+            self.get(row, col).is_none()
+        })
+    }
+
     pub fn place_word(
         &mut self,
         word: &Word,
@@ -81,3 +286,42 @@ impl Grid {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(rows: &[&str]) -> GridData {
+        GridData {
+            width: rows[0].chars().count(),
+            height: rows.len(),
+            rows: rows.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn round_trips_letters_blanks_and_mask() {
+        let grid = Grid::try_from(data(&["AB.", "C D"])).unwrap();
+        assert_eq!(grid.get(0, 1), Some('B'));
+        assert!(!grid.is_active(0, 2));
+        assert!(grid.is_active(1, 1) && grid.get(1, 1).is_none());
+        assert_eq!(GridData::from(grid).rows, vec!["AB.", "C D"]);
+    }
+
+    #[test]
+    fn rectangular_grids_keep_width_and_height_apart() {
+        let grid = Grid::new(6, 2);
+        assert_eq!(grid.longest_line(), 6);
+        let slots = grid.candidate_slots(&Word::new("PIZZA"), &Direction::ALL);
+        assert!(slots.iter().all(|s| matches!(s.direction, Direction::Horizontal | Direction::HorizontalReverse)));
+        assert_eq!(slots.len(), 8);
+        assert!(grid.candidate_slots(&Word::new("SAL"), &[Direction::Vertical]).is_empty());
+    }
+
+    #[test]
+    fn rejects_letters_outside_a_to_z() {
+        let error = Grid::try_from(data(&["ABC", "DÇF"])).unwrap_err();
+        assert!(error.contains("linha 2, coluna 2"), "{}", error);
+        assert!(Grid::try_from(data(&["abc"])).is_err());
+    }
+}
@@ -0,0 +1,123 @@
+use serde::{Deserialize, Serialize};
+
+use crate::direction::Direction;
+use crate::grid::{Grid, PlacementResult};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HintKind {
+    Quadrant,
+    Direction,
+    StartCell,
+}
+
+/// Uma dica sobre uma palavra: o texto completo para mensagens e uma forma curta para listas.
+#[derive(Debug, Clone)]
+pub struct Hint {
+    pub text: String,
+    pub short: String,
+}
+
+impl HintKind {
+    /// Ordem em que as dicas de uma palavra são reveladas, da mais vaga à mais precisa.
+    pub const PROGRESSION: [HintKind; 3] = [HintKind::Quadrant, HintKind::Direction, HintKind::StartCell];
+
+    pub fn cost(self) -> u32 {
+        match self {
+            HintKind::Quadrant => 10,
+            HintKind::Direction => 15,
+            HintKind::StartCell => 25,
+        }
+    }
+
+    /// Próxima dica da progressão que ainda não foi usada.
+    pub fn next(used: &[HintKind]) -> Option<HintKind> {
+        Self::PROGRESSION.into_iter().find(|kind| !used.contains(kind))
+    }
+}
+
+pub fn hint(grid: &Grid, placement: &PlacementResult, kind: HintKind) -> Hint {
+    let (text, short) = match kind {
+        HintKind::Quadrant => {
+            let name = quadrant_name(grid, placement);
+            (format!("fica na parte {} do grid", name), name.to_string())
+        }
+        HintKind::Direction => {
+            let (name, arrow) = direction_name(placement.direction);
+            (format!("está escrita {} ({})", name, arrow), arrow.to_string())
+        }
+        HintKind::StartCell => {
+            let (row, col) = start_coordinates(placement);
+            (format!("começa na linha {}, coluna {}", row, col), format!("L{} C{}", row, col))
+        }
+    };
+    Hint { text, short }
+}
+
+/// Linha e coluna da primeira letra, contando a partir de 1 como nas folhas impressas.
+pub fn start_coordinates(placement: &PlacementResult) -> (usize, usize) {
+    (placement.row + 1, placement.col + 1)
+}
+
+/// Quadrante com mais letras da palavra; no empate, o da primeira letra.
+fn quadrant_name(grid: &Grid, placement: &PlacementResult) -> &'static str {
+    let quadrant = |(row, col): (usize, usize)| (row * 2 >= grid.height) as usize * 2 + (col * 2 >= grid.width) as usize;
+    let positions = placement.positions();
+    let mut counts = [0usize; 4];
+    for &position in &positions {
+        counts[quadrant(position)] += 1;
+    }
+    let first = positions.first().map_or(0, |&position| quadrant(position));
+    let best = (0..4).fold(first, |best, q| if counts[q] > counts[best] { q } else { best });
+
+    match best {
+        0 => "superior esquerda",
+        1 => "superior direita",
+        2 => "inferior esquerda",
+        _ => "inferior direita",
+    }
+}
+
+fn direction_name(direction: Direction) -> (&'static str, &'static str) {
+    match direction {
+        Direction::Horizontal => ("da esquerda para a direita", "→"),
+        Direction::HorizontalReverse => ("da direita para a esquerda", "←"),
+        Direction::Vertical => ("de cima para baixo", "↓"),
+        Direction::VerticalReverse => ("de baixo para cima", "↑"),
+        Direction::DiagonalDown => ("na diagonal, descendo para a direita", "↘"),
+        Direction::DiagonalDownReverse => ("na diagonal, subindo para a esquerda", "↖"),
+        Direction::DiagonalUp => ("na diagonal, subindo para a direita", "↗"),
+        Direction::DiagonalUpReverse => ("na diagonal, descendo para a esquerda", "↙"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word::Word;
+
+    fn placement(word: &str, row: usize, col: usize, direction: Direction) -> PlacementResult {
+        PlacementResult { word: Word::new(word), row, col, direction }
+    }
+
+    #[test]
+    fn start_coordinates_count_from_one() {
+        assert_eq!(start_coordinates(&placement("OREGANO", 12, 6, Direction::Vertical)), (13, 7));
+    }
+
+    #[test]
+    fn quadrant_follows_most_letters() {
+        let grid = Grid::new(10, 10);
+        let region = |p: PlacementResult| hint(&grid, &p, HintKind::Quadrant).short;
+        assert_eq!(region(placement("PIZZA", 8, 1, Direction::DiagonalUp)), "inferior esquerda");
+        assert_eq!(region(placement("QUEIJO", 1, 9, Direction::HorizontalReverse)), "superior direita");
+        assert_eq!(region(placement("", 9, 9, Direction::Horizontal)), "superior esquerda");
+    }
+
+    #[test]
+    fn hints_progress_from_vague_to_precise() {
+        assert_eq!(HintKind::next(&[]), Some(HintKind::Quadrant));
+        assert_eq!(HintKind::next(&[HintKind::Quadrant]), Some(HintKind::Direction));
+        assert_eq!(HintKind::next(&HintKind::PROGRESSION), None);
+    }
+}
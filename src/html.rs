@@ -0,0 +1,194 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::hint::{self, HintKind};
+use crate::puzzle::PuzzleFile;
+use crate::render::RenderReport;
+
+const TEMPLATE: &str = include_str!("../assets/html/puzzle.html");
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+#[derive(Serialize)]
+struct PageData<'a> {
+    title: &'a str,
+    /// Texto, porque números do JSON acima de 2^53 perdem precisão no JavaScript.
+    seed: String,
+    rows: Vec<String>,
+    words: Vec<&'a str>,
+    key: u32,
+    answers: String,
+    hints: String,
+}
+
+#[derive(Serialize)]
+struct PageHint {
+    cost: u32,
+    text: String,
+    start: bool,
+}
+
+/// Grava uma página HTML autocontida em que o puzzle pode ser resolvido no navegador,
+/// arrastando o mouse (ou o dedo) da primeira à última letra de cada palavra.
+pub fn generate(puzzle: &PuzzleFile, path: &Path) -> Result<RenderReport, String> {
+    let rows = puzzle
+        .grid
+        .cells
+        .iter()
+        .enumerate()
+        .map(|(row, cells)| {
+            cells
+                .iter()
+                .enumerate()
+                .map(|(col, cell)| match cell {
+                    Some(ch) if puzzle.grid.is_active(row, col) => *ch,
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect();
+
+    let key = (puzzle.seed ^ (puzzle.seed >> 32)) as u32 | 1;
+    let data = PageData {
+        title: &puzzle.title,
+        seed: puzzle.seed.to_string(),
+        rows,
+        words: puzzle.placements.iter().map(|p| p.word.original.as_str()).collect(),
+        key,
+        answers: obfuscate(&encode_answers(puzzle), key),
+        hints: obfuscate(&encode_hints(puzzle)?, key),
+    };
+
+    let json = serde_json::to_string(&data).map_err(|e| e.to_string())?.replace("</", "<\\/");
+    let title = escape(&puzzle.title);
+    let html = fill_template(TEMPLATE, &[("{{TITLE}}", &title), ("{{DATA}}", &json)]);
+    fs::write(path, html).map_err(|e| e.to_string())?;
+
+    Ok(RenderReport {
+        pages: 1,
+        warnings: Vec::new(),
+        files: vec![path.to_path_buf()],
+    })
+}
+
+/// Troca os marcadores numa única passada pelo modelo, para que um título contendo
+/// `{{DATA}}` (ou vice-versa) não seja substituído de novo.
+fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut html = String::with_capacity(template.len() + values.iter().map(|(_, v)| v.len()).sum::<usize>());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        html.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match values.iter().find(|(marker, _)| rest.starts_with(marker)) {
+            Some((marker, value)) => {
+                html.push_str(value);
+                rest = &rest[marker.len()..];
+            }
+            None => {
+                html.push_str("{{");
+                rest = &rest[2..];
+            }
+        }
+    }
+    html.push_str(rest);
+    html
+}
+
+/// Uma palavra por item, como `linha,coluna,dl,dc,tamanho`, na ordem da lista.
+fn encode_answers(puzzle: &PuzzleFile) -> String {
+    puzzle
+        .placements
+        .iter()
+        .map(|p| {
+            let (dr, dc) = p.direction.deltas();
+            format!("{},{},{},{},{}", p.row, p.col, dr, dc, p.word.len())
+        })
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// As dicas de cada palavra, na ordem em que o botão "Dica" as revela.
+fn encode_hints(puzzle: &PuzzleFile) -> Result<String, String> {
+    let hints: Vec<Vec<PageHint>> = puzzle
+        .placements
+        .iter()
+        .map(|placement| {
+            HintKind::PROGRESSION
+                .into_iter()
+                .map(|kind| PageHint {
+                    cost: kind.cost(),
+                    text: hint::hint(&puzzle.grid, placement, kind).text,
+                    start: kind == HintKind::StartCell,
+                })
+                .collect()
+        })
+        .collect();
+    serde_json::to_string(&hints).map_err(|e| e.to_string())
+}
+
+/// Embaralha o texto com um xorshift para que respostas e dicas não fiquem legíveis no
+/// código-fonte da página. Não é criptografia: o próprio script da página desfaz a troca.
+fn obfuscate(text: &str, key: u32) -> String {
+    let mut state = key;
+    let bytes: Vec<u8> = text
+        .bytes()
+        .map(|b| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            b ^ (state & 0xff) as u8
+        })
+        .collect();
+    base64(&bytes)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::direction::Direction;
+    use crate::grid::Grid;
+    use crate::word::Word;
+
+    #[test]
+    fn fills_placeholders_in_a_single_pass() {
+        let html = fill_template("<h1>{{TITLE}}</h1>{{DATA}}{{x}}", &[("{{TITLE}}", "{{DATA}}"), ("{{DATA}}", "{}")]);
+        assert_eq!(html, "<h1>{{DATA}}</h1>{}{{x}}");
+    }
+
+    #[test]
+    fn writes_the_seed_as_a_string() {
+        let mut grid = Grid::new(5, 1);
+        let placement = grid.place_word(&Word::new("PIZZA"), 0, 0, Direction::Horizontal);
+        let words = vec!["PIZZA".to_string()];
+        let puzzle = PuzzleFile::new("Teste", 1760672345123456789, Difficulty::easy(), words, grid, vec![placement]);
+
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-{}.html", std::process::id()));
+        generate(&puzzle, &path).unwrap();
+        let html = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(html.contains(r#""seed":"1760672345123456789""#));
+    }
+}
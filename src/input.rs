@@ -1,12 +1,18 @@
 use std::io::{self, BufRead, Write};
+use std::path::Path;
 
 use crate::difficulty::Difficulty;
+use crate::mask::{Mask, Shape};
+use crate::wordlist::{dedup_words, load_word_list};
 
 #[derive(Debug, Clone)]
 pub struct PuzzleInput {
     pub title: String,
     pub difficulty: Difficulty,
-    pub grid_size: usize,
+    pub width: usize,
+    pub height: usize,
+    pub mask: Option<Mask>,
+    pub seed: Option<u64>,
     pub words: Vec<String>,
 }
 
@@ -43,46 +49,115 @@ impl PuzzleInput {
         println!("  1. 12x12 (pequeno)");
         println!("  2. 15x15 (médio)");
         println!("  3. 20x20 (grande)");
-        print!("Escolha (1-3): ");
+        println!("  4. Personalizado (largura x altura)");
+        print!("Escolha (1-4): ");
         stdout.flush()?;
         let size_choice = read_line(&stdin)?;
-        let grid_size = match size_choice.trim() {
-            "1" => 12,
-            "2" => 15,
-            "3" => 20,
+        let (width, height) = match size_choice.trim() {
+            "1" => (12, 12),
+            "2" => (15, 15),
+            "3" => (20, 20),
+            "4" => {
+                let width = read_dimension(&stdin, "Largura: ")?;
+                let height = read_dimension(&stdin, "Altura: ")?;
+                match (width, height) {
+                    (Some(width), Some(height)) => (width, height),
+                    _ => {
+                        println!("Dimensões inválidas, usando 15x15.");
+                        (15, 15)
+                    }
+                }
+            }
             _ => {
                 println!("Opção inválida, usando 15x15.");
-                15
+                (15, 15)
             }
         };
 
-        println!("\nDigite as palavras (uma por linha OU separadas por vírgula).");
-        println!("Quando terminar, digite uma linha vazia ou 'FIM':");
-        println!();
+        println!("\nFormato:");
+        println!("  1. Retangular (padrão)");
+        println!("  2. Círculo");
+        println!("  3. Fatia de pizza");
+        println!("  4. Losango");
+        println!("  5. Coração");
+        println!("  6. Carregar máscara de arquivo");
+        print!("Escolha (1-6): ");
+        stdout.flush()?;
+        let shape_choice = read_line(&stdin)?;
+        let mask = match shape_choice.trim() {
+            "" | "1" => None,
+            "2" => Some(Mask::from_shape(Shape::Circle, width, height)),
+            "3" => Some(Mask::from_shape(Shape::Slice, width, height)),
+            "4" => Some(Mask::from_shape(Shape::Diamond, width, height)),
+            "5" => Some(Mask::from_shape(Shape::Heart, width, height)),
+            "6" => {
+                print!("Arquivo da máscara: ");
+                stdout.flush()?;
+                let path = read_line(&stdin)?;
+                match Mask::from_file(Path::new(path.trim())) {
+                    Ok(mask) => Some(mask),
+                    Err(e) => {
+                        println!("Não foi possível ler a máscara ({}), usando formato retangular.", e);
+                        None
+                    }
+                }
+            }
+            _ => {
+                println!("Opção inválida, usando formato retangular.");
+                None
+            }
+        };
+
+        print!("\nSeed (Enter para aleatória): ");
+        stdout.flush()?;
+        let seed_choice = read_line(&stdin)?;
+        let seed = match seed_choice.trim() {
+            "" => None,
+            value => match value.parse::<u64>() {
+                Ok(seed) => Some(seed),
+                Err(_) => {
+                    println!("Seed inválida, usando uma aleatória.");
+                    None
+                }
+            },
+        };
+
+        println!("\nPalavras:");
+        println!("  1. Digitar");
+        println!("  2. Carregar de arquivo (TXT, CSV ou JSON)");
+        print!("Escolha (1-2): ");
+        stdout.flush()?;
+        let source_choice = read_line(&stdin)?;
 
         let mut words = Vec::new();
-        loop {
-            print!("> ");
+        if source_choice.trim() == "2" {
+            print!("Arquivo de palavras: ");
             stdout.flush()?;
-            let line = read_line(&stdin)?;
-            let trimmed = line.trim();
+            let path = read_line(&stdin)?;
+            words = load_word_list(Path::new(path.trim()))?
+                .into_iter()
+                .map(|entry| entry.word)
+                .collect();
+        } else {
+            println!("\nDigite as palavras (uma por linha OU separadas por vírgula).");
+            println!("Quando terminar, digite uma linha vazia ou 'FIM':");
+            println!();
 
-            if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("FIM") {
-                break;
-            }
+            loop {
+                print!("> ");
+                stdout.flush()?;
+                let line = read_line(&stdin)?;
+                let trimmed = line.trim();
 
-            if trimmed.contains(',') {
-                for word in trimmed.split(',') {
-                    let w = word.trim();
-                    if !w.is_empty() {
-                        words.push(w.to_string());
-                    }
+                if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("FIM") {
+                    break;
                 }
-            } else if !trimmed.is_empty() {
-                words.push(trimmed.to_string());
+
+                push_words(trimmed, &mut words);
             }
         }
 
+        let words = dedup_words(words);
         if words.is_empty() {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -95,12 +170,31 @@ impl PuzzleInput {
         Ok(Self {
             title,
             difficulty,
-            grid_size,
+            width,
+            height,
+            mask,
+            seed,
             words,
         })
     }
 }
 
+fn push_words(line: &str, words: &mut Vec<String>) {
+    for word in line.split(',') {
+        let w = word.trim();
+        if !w.is_empty() {
+            words.push(w.to_string());
+        }
+    }
+}
+
+fn read_dimension(stdin: &io::Stdin, prompt: &str) -> io::Result<Option<usize>> {
+    print!("{}", prompt);
+    io::stdout().flush()?;
+    let value = read_line(stdin)?;
+    Ok(value.trim().parse::<usize>().ok().filter(|&n| n > 0))
+}
+
 fn read_line(stdin: &io::Stdin) -> io::Result<String> {
     let mut line = String::new();
     stdin.lock().read_line(&mut line)?;
@@ -1,39 +1,466 @@
+mod backtrack;
+mod blocklist;
+mod booklet;
+mod cli;
+mod decoy;
 mod difficulty;
 mod direction;
+mod error;
 mod filler;
+mod font;
 mod generator;
 mod grid;
+mod hint;
+mod html;
 mod input;
+mod mask;
 mod pdf;
+mod play;
+mod png;
+mod puzzle;
+mod render;
+mod solver;
+mod svg;
 mod word;
+mod wordlist;
 
-use generator::{Generator, GeneratorConfig};
+use clap::Parser;
+use blocklist::Blocklist;
+use booklet::BookletManifest;
+use cli::{BookletArgs, Cli, Command, GenerateArgs, OutputArgs, PlayArgs, RenderArgs, SolveArgs};
+use error::AppError;
+use font::FontSet;
+use generator::{random_seed, seeded_rng, Generator, GeneratorConfig, PlacementOutcome};
 use input::PuzzleInput;
-use pdf::PdfGenerator;
-use rand::rngs::StdRng;
-use rand::SeedableRng;
+use mask::Mask;
+use play::{PlayOutcome, PlaySession};
+use puzzle::PuzzleFile;
+use render::{AnswerStyle, OutputFormat, PageComposer, PageFormat, RenderReport};
+use solver::Solver;
+use std::collections::HashSet;
 use std::fs;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
 
-fn main() {
-    let input = match PuzzleInput::from_stdin() {
-        Ok(i) => i,
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let outcome = match cli.command.unwrap_or(Command::Interactive) {
+        Command::Generate(args) => run_generate(*args),
+        Command::Render(args) => run_render(args),
+        Command::Booklet(args) => run_booklet(args),
+        Command::Play(args) => run_play(args),
+        Command::Solve(args) => run_solve(args),
+        Command::Verify(args) => run_verify(args),
+        Command::Interactive => run_interactive(),
+    };
+
+    match outcome {
+        Ok(()) => ExitCode::SUCCESS,
         Err(e) => {
-            eprintln!("Erro ao ler entrada: {}", e);
-            std::process::exit(1);
+            eprintln!("\n❌ {}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+fn run_interactive() -> Result<(), AppError> {
+    let input = PuzzleInput::from_stdin().map_err(|e| AppError::BadInput(e.to_string()))?;
+    let config = base_config(&input);
+    let output = OutputOptions {
+        dir: None,
+        allow_discarded: true,
+        page: PageFormat::default(),
+        formats: vec![OutputFormat::Pdf],
+        dpi: 150,
+        fonts: FontSet::default(),
+        answer_style: AnswerStyle::default(),
+        hint_sheet: false,
+        verbose: false,
+    };
+    run_puzzle(&input, config, &output)
+}
+
+fn run_generate(args: GenerateArgs) -> Result<(), AppError> {
+    let words = load_words(&args.words, args.words_file.as_deref())?;
+    let (width, height) = args.size;
+    let mask = match (&args.mask_file, args.shape) {
+        (Some(path), _) => Some(
+            Mask::from_file(path).map_err(|e| AppError::BadInput(format!("{}: {}", path.display(), e)))?,
+        ),
+        (None, Some(shape)) => Some(Mask::from_shape(shape.to_shape(), width, height)),
+        (None, None) => None,
+    };
+
+    let mut difficulty = args.difficulty;
+    if let Some(spec) = &args.directions {
+        difficulty.set_directions(spec).map_err(|e| AppError::BadInput(format!("--directions: {}", e)))?;
+    }
+    if let Some(density) = args.decoys {
+        if !(0.0..=1.0).contains(&density) {
+            return Err(AppError::BadInput("--decoys deve estar entre 0.0 e 1.0".to_string()));
+        }
+        difficulty.decoy_density = density;
+    }
+    if let Some(ratio) = args.overlap {
+        if !(0.0..=1.0).contains(&ratio) {
+            return Err(AppError::BadInput("--overlap deve estar entre 0.0 e 1.0".to_string()));
         }
+        difficulty.min_overlap = ratio;
+        difficulty.max_overlap = ratio;
+    }
+    if let Some(strategy) = args.filler_strategy {
+        difficulty.filler_strategy = strategy.to_strategy();
+    }
+    let filler_strategy = difficulty.filler_strategy;
+
+    let input = PuzzleInput {
+        title: args.title,
+        difficulty,
+        width,
+        height,
+        mask,
+        seed: args.seed,
+        words,
+    };
+
+    let mut config = base_config(&input)
+        .with_strategy(args.strategy.to_strategy())
+        .with_search_budget(args.max_steps, args.time_limit_ms.map(Duration::from_millis))
+        .with_filler(args.filler, filler_strategy);
+    if let Some(message) = &args.message {
+        config = config.with_message(message);
+    }
+    if args.no_blocklist {
+        config = config.with_blocklist(Blocklist::default());
+    } else if let Some(path) = &args.blocklist {
+        let mut blocklist = Blocklist::bundled();
+        blocklist
+            .extend_from_file(path)
+            .map_err(|e| AppError::BadInput(format!("{}: {}", path.display(), e)))?;
+        config = config.with_blocklist(blocklist);
+    }
+
+    let mut output = OutputOptions::from_args(args.out, args.allow_discarded, &args.output)?;
+    output.verbose = args.verbose;
+    run_puzzle(&input, config, &output)
+}
+
+fn run_render(args: RenderArgs) -> Result<(), AppError> {
+    let mut puzzle = PuzzleFile::load(&args.puzzle)
+        .map_err(|e| AppError::BadInput(format!("{}: {}", args.puzzle.display(), e)))?;
+    if let Some(title) = args.title {
+        puzzle.title = title;
+    }
+
+    let dir = args.out.unwrap_or_else(|| default_output_dir(&puzzle.title));
+    let output = OutputOptions::from_args(Some(dir.clone()), true, &args.output)?;
+    render_outputs(&puzzle, &dir, &output)?;
+
+    let formats: Vec<String> = output.formats.iter().map(|f| f.extension().to_uppercase()).collect();
+    println!("\n✅ Arquivos {} gerados a partir de {}", formats.join(", "), args.puzzle.display());
+    Ok(())
+}
+
+fn run_booklet(args: BookletArgs) -> Result<(), AppError> {
+    let manifest = BookletManifest::load(&args.manifest)
+        .map_err(|e| AppError::BadInput(format!("{}: {}", args.manifest.display(), e)))?;
+    let base_dir = args.manifest.parent().unwrap_or(Path::new("."));
+    let inputs = manifest.puzzle_inputs(base_dir, args.seed).map_err(AppError::BadInput)?;
+
+    let dir = args.out.clone().unwrap_or_else(|| default_output_dir(&manifest.title));
+    let output = OutputOptions::from_args(Some(dir.clone()), args.allow_discarded, &args.output)?;
+    create_output_dir(&dir)?;
+
+    println!("\n📚 Caderno \"{}\" com {} puzzles\n", manifest.title, inputs.len());
+
+    let mut puzzles = Vec::new();
+    let mut discarded = Vec::new();
+    let mut repeated = Vec::new();
+    for (i, input) in inputs.iter().enumerate() {
+        let seed = input.seed.unwrap_or_else(random_seed);
+        let words_refs: Vec<&str> = input.words.iter().map(|s| s.as_str()).collect();
+        let result = Generator::new(base_config(input)).generate(&words_refs, &mut seeded_rng(seed));
+
+        println!(
+            "  {}. {} — {}/{} palavras (seed {})",
+            i + 1,
+            input.title,
+            result.placed_words.len(),
+            input.words.len(),
+            seed
+        );
+        for word in &result.discarded_words {
+            println!("     ✗ {}", word.original);
+            discarded.push(format!("{} ({})", word.original, input.title));
+        }
+        for occurrence in result.repeated() {
+            println!("     ⚠️  {} aparece {} vezes", occurrence.word.original, occurrence.count);
+            repeated.push(format!("{} ({})", occurrence.word.original, input.title));
+        }
+
+        let puzzle = PuzzleFile::new(
+            &input.title,
+            seed,
+            input.difficulty.clone(),
+            input.words.clone(),
+            result.grid,
+            result.placed_words,
+        );
+        let puzzle_path = dir.join(format!("puzzle-{:02}.json", i + 1));
+        puzzle.save(&puzzle_path)
+            .map_err(|e| AppError::FileWrite(format!("{}: {}", puzzle_path.display(), e)))?;
+        puzzles.push(puzzle);
+    }
+    println!("\n💾 Puzzles salvos em {}/puzzle-NN.json", dir.display());
+    if !repeated.is_empty() {
+        return Err(AppError::VerificationFailed(repeated));
+    }
+
+    println!("\n📄 Gerando caderno em {}/ ...", dir.display());
+    let composer = output.composer(&manifest.title);
+    for &format in &output.formats {
+        if format == OutputFormat::Html {
+            for (i, puzzle) in puzzles.iter().enumerate() {
+                let path = dir.join(format!("puzzle-{:02}.html", i + 1));
+                let report = html::generate(puzzle, &path)
+                    .map_err(|e| AppError::RenderWrite(format!("{}: {}", path.display(), e)))?;
+                print_report("Página interativa", &report);
+            }
+            continue;
+        }
+
+        let path = dir.join(format!("caderno.{}", format.extension()));
+        let report = composer
+            .generate_booklet(format, &puzzles, args.keys_per_page, &path)
+            .map_err(|e| AppError::RenderWrite(format!("{}: {}", path.display(), e)))?;
+        print_report("Caderno", &report);
+
+        if output.hint_sheet {
+            for (i, puzzle) in puzzles.iter().enumerate() {
+                let path = dir.join(format!("dicas-{:02}.{}", i + 1, format.extension()));
+                let report = output
+                    .composer(&puzzle.title)
+                    .with_seed(puzzle.seed)
+                    .generate_hint_sheet(format, &puzzle.grid, &puzzle.placements, &path)
+                    .map_err(|e| AppError::RenderWrite(format!("{}: {}", path.display(), e)))?;
+                print_report("Dicas", &report);
+            }
+        }
+    }
+
+    if !output.allow_discarded && !discarded.is_empty() {
+        return Err(AppError::Unplaceable(discarded));
+    }
+
+    println!("\n✅ Caderno concluído!");
+    Ok(())
+}
+
+fn run_play(args: PlayArgs) -> Result<(), AppError> {
+    let (session, resumed) = PlaySession::open(&args.file)
+        .map_err(|e| AppError::BadInput(format!("{}: {}", args.file.display(), e)))?;
+    if session.puzzle.placements.is_empty() {
+        return Err(AppError::BadInput(format!("{}: o puzzle não tem palavras posicionadas", args.file.display())));
+    }
+    let session_path = args.session.unwrap_or_else(|| match resumed {
+        true => args.file.clone(),
+        false => args.file.with_file_name("sessao.json"),
+    });
+
+    let (session, outcome) = play::run(session, &session_path).map_err(|e| AppError::Terminal(e.to_string()))?;
+
+    match outcome {
+        PlayOutcome::Completed => println!(
+            "🏆 {}: {} palavras em {}, {} pontos",
+            session.puzzle.title,
+            session.found.len(),
+            play::clock(session.elapsed_secs),
+            session.score()
+        ),
+        PlayOutcome::Paused => {
+            println!(
+                "⏸️  {}/{} palavras, {} pontos. Sessão salva em {}",
+                session.found.len(),
+                session.puzzle.placements.len(),
+                session.score(),
+                session_path.display()
+            );
+            println!("   Para continuar: pizza-wordsearch play {}", session_path.display());
+        }
+    }
+    Ok(())
+}
+
+fn run_solve(args: SolveArgs) -> Result<(), AppError> {
+    let (grid, words) = load_solve_input(&args)?;
+    let solver = Solver::new(&words);
+
+    println!("🔎 Procurando {} palavras em um grid {}x{}...\n", solver.words().len(), grid.width, grid.height);
+
+    for (word, occurrences) in solver.words().iter().zip(solver.find_grouped(&grid)) {
+        if occurrences.is_empty() {
+            println!("  ✗ {} não encontrada", word.original);
+        }
+        for occurrence in occurrences {
+            println!("  ✓ {} @ ({}, {}) {:?}", word.original, occurrence.row, occurrence.col, occurrence.direction);
+        }
+    }
+
+    Ok(())
+}
+
+fn run_verify(args: SolveArgs) -> Result<(), AppError> {
+    let (grid, words) = load_solve_input(&args)?;
+    let solver = Solver::new(&words);
+    let mut failures = Vec::new();
+
+    for (word, occurrences) in solver.words().iter().zip(solver.find_grouped(&grid)) {
+        if occurrences.len() == 1 {
+            println!("  ✓ {} (1x)", word.original);
+        } else {
+            println!("  ✗ {} ({}x)", word.original, occurrences.len());
+            failures.push(word.original.clone());
+        }
+    }
+
+    if !failures.is_empty() {
+        return Err(AppError::VerificationFailed(failures));
+    }
+
+    println!("\n✅ Todas as palavras aparecem exatamente uma vez.");
+    Ok(())
+}
+
+fn load_solve_input(args: &SolveArgs) -> Result<(grid::Grid, Vec<word::Word>), AppError> {
+    let bad_grid = |e: String| AppError::BadInput(format!("{}: {}", args.grid.display(), e));
+
+    let (grid, saved_words) = if args.grid.extension().is_some_and(|ext| ext == "json") {
+        let puzzle = PuzzleFile::load(&args.grid).map_err(|e| bad_grid(e.to_string()))?;
+        (puzzle.grid, puzzle.words)
+    } else {
+        let text = fs::read_to_string(&args.grid).map_err(|e| bad_grid(e.to_string()))?;
+        (grid::Grid::from_text(&text).map_err(bad_grid)?, Vec::new())
+    };
+
+    let words = if args.words.is_empty() && args.words_file.is_none() && !saved_words.is_empty() {
+        saved_words
+    } else {
+        load_words(&args.words, args.words_file.as_deref())?
     };
 
-    let seed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
-    let config = GeneratorConfig::new(input.grid_size, input.difficulty.clone()).with_max_attempts(200);
+    Ok((grid, words.iter().map(|w| word::Word::new(w)).collect()))
+}
+
+fn load_words(words: &[String], words_file: Option<&Path>) -> Result<Vec<String>, AppError> {
+    let mut words: Vec<String> = words.iter()
+        .map(|w| w.trim().to_string())
+        .filter(|w| !w.is_empty())
+        .collect();
+    if let Some(path) = words_file {
+        let entries = wordlist::load_word_list(path)
+            .map_err(|e| AppError::BadInput(format!("{}: {}", path.display(), e)))?;
+        let categories: HashSet<&str> = entries.iter().filter_map(|e| e.category.as_deref()).collect();
+        let with_clues = entries.iter().filter(|e| e.clue.is_some()).count();
+        println!("📚 {} palavras carregadas de {} ({} com dica, {} categorias)",
+            entries.len(), path.display(), with_clues, categories.len());
+        words.extend(entries.into_iter().map(|e| e.word));
+    }
+    let words = wordlist::dedup_words(words);
+
+    if words.is_empty() {
+        return Err(AppError::BadInput("nenhuma palavra fornecida (use --words ou --words-file)".to_string()));
+    }
+    Ok(words)
+}
+
+fn load_fonts(path: Option<&Path>) -> Result<FontSet, AppError> {
+    match path {
+        Some(path) => FontSet::from_file(path).map_err(|e| AppError::BadInput(format!("{}: {}", path.display(), e))),
+        None => Ok(FontSet::bundled()),
+    }
+}
+
+fn base_config(input: &PuzzleInput) -> GeneratorConfig {
+    let config = GeneratorConfig::new(input.width, input.height, input.difficulty.clone()).with_max_attempts(200);
+    match &input.mask {
+        Some(mask) => config.with_mask(mask.clone()),
+        None => config,
+    }
+}
+
+struct OutputOptions {
+    dir: Option<PathBuf>,
+    allow_discarded: bool,
+    page: PageFormat,
+    formats: Vec<OutputFormat>,
+    dpi: u32,
+    fonts: FontSet,
+    answer_style: AnswerStyle,
+    hint_sheet: bool,
+    verbose: bool,
+}
+
+impl OutputOptions {
+    fn from_args(dir: Option<PathBuf>, allow_discarded: bool, args: &OutputArgs) -> Result<Self, AppError> {
+        let mut formats: Vec<OutputFormat> = Vec::new();
+        for format in args.format.iter().map(|f| f.to_format()) {
+            if !formats.contains(&format) {
+                formats.push(format);
+            }
+        }
+        Ok(Self {
+            dir,
+            allow_discarded,
+            page: PageFormat::new(args.page, args.orientation.to_orientation()),
+            formats,
+            dpi: args.dpi,
+            fonts: load_fonts(args.font.as_deref())?,
+            answer_style: args.answer_style.to_answer_style(),
+            hint_sheet: args.hint_sheet,
+            verbose: false,
+        })
+    }
+
+    fn composer(&self, title: &str) -> PageComposer {
+        PageComposer::new(title)
+            .with_page(self.page)
+            .with_dpi(self.dpi)
+            .with_fonts(self.fonts.clone())
+            .with_answer_style(self.answer_style)
+    }
+}
+
+fn run_puzzle(input: &PuzzleInput, config: GeneratorConfig, output: &OutputOptions) -> Result<(), AppError> {
+    let seed = input.seed.unwrap_or_else(random_seed);
+    let message = config.message.clone();
+    if message.is_some() && config.difficulty.decoy_density > 0.0 {
+        println!("⚠️  A mensagem secreta ocupa todas as células livres; as iscas da dificuldade não serão plantadas.");
+    }
     let generator = Generator::new(config);
 
     let words_refs: Vec<&str> = input.words.iter().map(|s| s.as_str()).collect();
-    let mut rng = StdRng::seed_from_u64(seed);
+    if let Some(message) = &message {
+        generator
+            .required_overlap(&words_refs, message)
+            .map_err(|e| AppError::BadInput(format!("mensagem secreta: {}", e)))?;
+    }
+    let mut rng = seeded_rng(seed);
     let result = generator.generate(&words_refs, &mut rng);
 
+    if message.is_some() && result.message.is_none() {
+        let words: Vec<String> = result.discarded_words.iter().map(|w| w.original.clone()).collect();
+        println!("\n⚠️  Não foi possível cruzar as palavras de modo a sobrarem exatamente as células da mensagem secreta.");
+        if result.outcome == PlacementOutcome::BudgetExhausted {
+            println!("   Limite da busca atingido; tente aumentar --max-steps ou --time-limit-ms.");
+        }
+        return Err(AppError::Unplaceable(words));
+    }
+
     println!("\n📊 Gerando caça-palavras...\n");
-    println!("Grid ({}x{}):\n", input.grid_size, input.grid_size);
+    println!("🎲 Seed: {}\n", seed);
+    println!("Grid ({}x{}):\n", result.grid.width, result.grid.height);
     print_grid(&result.grid);
 
     println!("\nPalavras encontradas ({}):", result.placed_words.len());
@@ -41,6 +468,35 @@ fn main() {
         println!("  ✓ {} @ ({}, {}) {:?}", placement.word.original, placement.row, placement.col, placement.direction);
     }
 
+    println!("\nCélulas compartilhadas: {}", result.shared_cells);
+
+    if let Some(message) = &result.message {
+        println!("Mensagem secreta: {}", message);
+    }
+
+    if !result.decoys.is_empty() {
+        let decoys: Vec<String> = result.decoys.iter().map(|d| format!("{} ({})", d.text, d.target.original)).collect();
+        println!("Iscas ({}): {}", decoys.len(), decoys.join(", "));
+    }
+
+    if output.verbose && !result.blocked.avoided.is_empty() {
+        println!("\nPalavras bloqueadas evitadas no preenchimento ({}):", result.blocked.avoided.len());
+        for occurrence in &result.blocked.avoided {
+            println!("  🚫 {} @ ({}, {}) {:?}", occurrence.word.normalized, occurrence.row, occurrence.col, occurrence.direction);
+        }
+    }
+    let formed_by = if result.message.is_some() { "pela mensagem secreta" } else { "pelas palavras do puzzle" };
+    for occurrence in &result.blocked.remaining {
+        println!("⚠️  Palavra bloqueada {} formada {} @ ({}, {})", occurrence.word.normalized, formed_by, occurrence.row, occurrence.col);
+    }
+    for occurrence in &result.blocked.decoys {
+        println!("⚠️  Palavra bloqueada {} formada com letras de uma isca @ ({}, {})", occurrence.word.normalized, occurrence.row, occurrence.col);
+    }
+
+    for occurrence in result.repeated() {
+        println!("⚠️  {} aparece {} vezes no grid", occurrence.word.original, occurrence.count);
+    }
+
     if !result.discarded_words.is_empty() {
         println!("\nPalavras descartadas ({}):", result.discarded_words.len());
         for word in &result.discarded_words {
@@ -48,44 +504,145 @@ fn main() {
         }
     }
 
-    let safe_title: String = input.title.chars()
-        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
-        .collect::<String>()
-        .replace(' ', "_")
-        .to_lowercase();
+    match result.outcome {
+        PlacementOutcome::AllPlaced | PlacementOutcome::Partial => {}
+        PlacementOutcome::ProvenImpossible => {
+            println!("\n⚠️  Busca completa: não existe arranjo que comporte todas as palavras.");
+        }
+        PlacementOutcome::BudgetExhausted => {
+            println!("\n⚠️  Limite da busca atingido antes de encontrar um arranjo completo.");
+        }
+    }
 
-    let pdf_dir = format!("pdf/{}", safe_title);
-    if let Err(e) = fs::create_dir_all(&pdf_dir) {
-        eprintln!("Erro ao criar diretório {}: {}", pdf_dir, e);
-        std::process::exit(1);
+    let repeated: Vec<String> = result.repeated().iter().map(|o| o.word.original.clone()).collect();
+    if !repeated.is_empty() {
+        println!("\n⚠️  Nenhuma tentativa deixou cada palavra exatamente uma vez no grid; tente outra seed.");
+        return Err(AppError::VerificationFailed(repeated));
     }
 
-    let puzzle_filename = format!("{}/puzzle.pdf", pdf_dir);
-    let answer_filename = format!("{}/gabarito.pdf", pdf_dir);
-    let pdf_gen = PdfGenerator::new(&input.title);
+    let discarded: Vec<String> = result.discarded_words.iter().map(|w| w.original.clone()).collect();
+    let puzzle = PuzzleFile::new(
+        &input.title,
+        seed,
+        input.difficulty.clone(),
+        input.words.clone(),
+        result.grid,
+        result.placed_words,
+    )
+    .with_message(result.message);
 
-    println!("\n📄 Gerando PDFs em {}/ ...", pdf_dir);
+    let pdf_dir = match &output.dir {
+        Some(dir) => dir.clone(),
+        None => default_output_dir(&input.title),
+    };
+    create_output_dir(&pdf_dir)?;
 
-    match pdf_gen.generate_puzzle(&result.grid, &input.words, &puzzle_filename) {
-        Ok(_) => println!("  ✓ Puzzle: {}", puzzle_filename),
-        Err(e) => eprintln!("  ✗ Erro ao gerar puzzle: {}", e),
-    }
+    let puzzle_path = pdf_dir.join("puzzle.json");
+    puzzle.save(&puzzle_path)
+        .map_err(|e| AppError::FileWrite(format!("{}: {}", puzzle_path.display(), e)))?;
+    println!("\n💾 Puzzle salvo em {}", puzzle_path.display());
+
+    render_outputs(&puzzle, &pdf_dir, output)?;
 
-    match pdf_gen.generate_answer_key(&result.grid, &result.placed_words, &answer_filename) {
-        Ok(_) => println!("  ✓ Gabarito: {}", answer_filename),
-        Err(e) => eprintln!("  ✗ Erro ao gerar gabarito: {}", e),
+    if !output.allow_discarded && !discarded.is_empty() {
+        return Err(AppError::Unplaceable(discarded));
     }
 
     println!("\n✅ Geração concluída!");
+    Ok(())
+}
+
+fn create_output_dir(dir: &Path) -> Result<(), AppError> {
+    fs::create_dir_all(dir)
+        .map_err(|e| AppError::FileWrite(format!("não foi possível criar {}: {}", dir.display(), e)))
+}
+
+fn render_outputs(puzzle: &PuzzleFile, dir: &Path, output: &OutputOptions) -> Result<(), AppError> {
+    create_output_dir(dir)?;
+
+    let composer = output.composer(&puzzle.title).with_seed(puzzle.seed);
+    println!("\n📄 Gerando arquivos em {}/ ...", dir.display());
+
+    let mut errors = Vec::new();
+    for &format in &output.formats {
+        if format == OutputFormat::Html {
+            let html_path = dir.join("puzzle.html");
+            match html::generate(puzzle, &html_path) {
+                Ok(report) => print_report("Página interativa", &report),
+                Err(e) => {
+                    eprintln!("  ✗ Erro ao gerar página interativa: {}", e);
+                    errors.push(format!("{}: {}", html_path.display(), e));
+                }
+            }
+            continue;
+        }
+
+        let puzzle_path = dir.join(format!("puzzle.{}", format.extension()));
+        match composer.generate_puzzle(format, &puzzle.grid, &puzzle.words, &puzzle_path) {
+            Ok(report) => print_report("Puzzle", &report),
+            Err(e) => {
+                eprintln!("  ✗ Erro ao gerar puzzle: {}", e);
+                errors.push(format!("{}: {}", puzzle_path.display(), e));
+            }
+        }
+
+        let answer_path = dir.join(format!("gabarito.{}", format.extension()));
+        match composer.generate_answer_key(format, &puzzle.grid, &puzzle.placements, puzzle.message.as_deref(), &answer_path) {
+            Ok(report) => print_report("Gabarito", &report),
+            Err(e) => {
+                eprintln!("  ✗ Erro ao gerar gabarito: {}", e);
+                errors.push(format!("{}: {}", answer_path.display(), e));
+            }
+        }
+
+        if output.hint_sheet {
+            let hints_path = dir.join(format!("dicas.{}", format.extension()));
+            match composer.generate_hint_sheet(format, &puzzle.grid, &puzzle.placements, &hints_path) {
+                Ok(report) => print_report("Dicas", &report),
+                Err(e) => {
+                    eprintln!("  ✗ Erro ao gerar folha de dicas: {}", e);
+                    errors.push(format!("{}: {}", hints_path.display(), e));
+                }
+            }
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::RenderWrite(errors.join("; ")));
+    }
+
+    Ok(())
+}
+
+fn print_report(label: &str, report: &RenderReport) {
+    let files: Vec<String> = report.files.iter().map(|f| f.display().to_string()).collect();
+    if report.pages > 1 {
+        println!("  ✓ {}: {} ({} páginas)", label, files.join(", "), report.pages);
+    } else {
+        println!("  ✓ {}: {}", label, files.join(", "));
+    }
+    for warning in &report.warnings {
+        println!("    ⚠️  {}", warning);
+    }
+}
+
+fn default_output_dir(title: &str) -> PathBuf {
+    let safe_title: String = title.chars()
+        .filter(|c| c.is_alphanumeric() || *c == ' ' || *c == '-' || *c == '_')
+        .collect::<String>()
+        .replace(' ', "_")
+        .to_lowercase();
+
+    PathBuf::from("pdf").join(safe_title)
 }
 
 fn print_grid(grid: &grid::Grid) {
-    let border = "─".repeat(grid.size * 2 + 1);
+    let border = "─".repeat(grid.width * 2 + 1);
     println!("┌{}┐", border);
     for row in &grid.cells {
         print!("│ ");
         for cell in row {
-            print!("{} ", cell.unwrap_or('.'));
+            print!("{} ", cell.unwrap_or(' '));
         }
         println!("│");
     }
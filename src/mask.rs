@@ -0,0 +1,144 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Circle,
+    Slice,
+    Diamond,
+    Heart,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mask {
+    pub width: usize,
+    pub height: usize,
+    active: Vec<Vec<bool>>,
+}
+
+impl Mask {
+    pub fn full(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            active: vec![vec![true; width]; height],
+        }
+    }
+
+    pub fn from_shape(shape: Shape, width: usize, height: usize) -> Self {
+        let active = (0..height)
+            .map(|row| {
+                (0..width)
+                    .map(|col| {
+                        let x = (col as f32 + 0.5) / width as f32 * 2.0 - 1.0;
+                        let y = (row as f32 + 0.5) / height as f32 * 2.0 - 1.0;
+                        shape.contains(x, y)
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { width, height, active }
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, String> {
+        let rows: Vec<Vec<bool>> = text
+            .lines()
+            .map(|line| line.trim_end())
+            .filter(|line| !line.is_empty())
+            .map(|line| line.chars().map(|c| !matches!(c, '.' | ' ' | '0' | '-' | '_')).collect())
+            .collect();
+
+        let height = rows.len();
+        let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        if width == 0 {
+            return Err("máscara vazia".to_string());
+        }
+
+        let active: Vec<Vec<bool>> = rows
+            .into_iter()
+            .map(|mut row| {
+                row.resize(width, false);
+                row
+            })
+            .collect();
+
+        let mask = Self { width, height, active };
+        if mask.active_count() == 0 {
+            return Err("a máscara não tem nenhuma célula ativa".to_string());
+        }
+        Ok(mask)
+    }
+
+    pub fn from_file(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        Self::from_text(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    pub fn is_active(&self, row: usize, col: usize) -> bool {
+        self.active.get(row).and_then(|r| r.get(col)).copied().unwrap_or(false)
+    }
+
+    pub fn set_active(&mut self, row: usize, col: usize, active: bool) {
+        if row < self.height && col < self.width {
+            self.active[row][col] = active;
+        }
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.active.iter().flatten().filter(|&&a| a).count()
+    }
+}
+
+impl Shape {
+    fn contains(&self, x: f32, y: f32) -> bool {
+        match self {
+            Shape::Circle => x * x + y * y <= 1.0,
+            Shape::Slice => x.abs() <= 1.05 - (y + 1.0) / 2.0,
+            Shape::Diamond => x.abs() + y.abs() <= 1.0,
+            Shape::Heart => {
+                let hx = x * 1.15;
+                let hy = 0.125 - y * 1.125;
+                let base = hx * hx + hy * hy - 1.0;
+                base * base * base - hx * hx * hy * hy * hy <= 0.0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_ascii_masks_and_pads_short_rows() {
+        let mask = Mask::from_text("..##\n.###\n##\n").unwrap();
+        assert_eq!((mask.width, mask.height), (4, 3));
+        assert!(!mask.is_active(0, 1) && mask.is_active(0, 2));
+        assert!(mask.is_active(2, 1) && !mask.is_active(2, 3));
+        assert_eq!(mask.active_count(), 7);
+    }
+
+    #[test]
+    fn rejects_masks_without_active_cells() {
+        assert!(Mask::from_text("").is_err());
+        assert!(Mask::from_text("....\n....").is_err());
+    }
+
+    #[test]
+    fn shapes_leave_corners_out_and_keep_the_centre() {
+        for shape in [Shape::Circle, Shape::Slice, Shape::Diamond, Shape::Heart] {
+            let mask = Mask::from_shape(shape, 12, 12);
+            assert!(mask.is_active(6, 6), "{:?}", shape);
+            assert!(!mask.is_active(11, 0) && !mask.is_active(11, 11), "{:?}", shape);
+            assert!(mask.active_count() < 144, "{:?}", shape);
+        }
+    }
+
+    #[test]
+    fn cells_outside_the_mask_are_inactive() {
+        let mask = Mask::full(3, 2);
+        assert!(mask.is_active(1, 2));
+        assert!(!mask.is_active(2, 0) && !mask.is_active(0, 3));
+    }
+}
@@ -1,176 +1,105 @@
 use printpdf::*;
 use std::fs::File;
 use std::io::BufWriter;
+use std::path::{Path, PathBuf};
 
-use crate::grid::{Grid, PlacementResult};
+use crate::font::FontSet;
+use crate::render::{self, FontStyle, PageFormat, Renderer, Stroke, PT_TO_MM};
 
-const PAGE_WIDTH_MM: f32 = 210.0;
-const PAGE_HEIGHT_MM: f32 = 297.0;
-const MARGIN_MM: f32 = 20.0;
-const CELL_SIZE_MM: f32 = 8.0;
-
-pub struct PdfGenerator {
-    title: String,
+pub struct PdfRenderer {
+    doc: PdfDocumentReference,
+    page: PageFormat,
+    layers: Vec<PdfLayerReference>,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
 }
 
-impl PdfGenerator {
-    pub fn new(title: &str) -> Self {
-        Self { title: title.to_string() }
-    }
-
-    pub fn generate_puzzle(&self, grid: &Grid, words: &[String], filename: &str) -> Result<(), String> {
-        let (doc, page1, layer1) = PdfDocument::new(&self.title, Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Layer 1");
-        let current_layer = doc.get_page(page1).get_layer(layer1);
-        let font = doc.add_builtin_font(BuiltinFont::Courier).map_err(|e| e.to_string())?;
-        let font_bold = doc.add_builtin_font(BuiltinFont::CourierBold).map_err(|e| e.to_string())?;
-
-        self.draw_title(&current_layer, &font_bold);
-        self.draw_grid(&current_layer, grid, &font, None);
-        self.draw_word_list(&current_layer, grid, words, &font);
-
-        let file = File::create(filename).map_err(|e| e.to_string())?;
-        doc.save(&mut BufWriter::new(file)).map_err(|e| e.to_string())?;
-        Ok(())
-    }
-
-    pub fn generate_answer_key(&self, grid: &Grid, placed_words: &[PlacementResult], filename: &str) -> Result<(), String> {
-        let (doc, page1, layer1) = PdfDocument::new(&format!("{} - Gabarito", self.title), Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Layer 1");
-        let current_layer = doc.get_page(page1).get_layer(layer1);
-        let font = doc.add_builtin_font(BuiltinFont::Courier).map_err(|e| e.to_string())?;
-        let font_bold = doc.add_builtin_font(BuiltinFont::CourierBold).map_err(|e| e.to_string())?;
-
-        self.draw_title_answer(&current_layer, &font_bold);
-        self.draw_grid(&current_layer, grid, &font, Some(placed_words));
-        self.draw_placed_words_list(&current_layer, grid, placed_words, &font);
-
-        let file = File::create(filename).map_err(|e| e.to_string())?;
-        doc.save(&mut BufWriter::new(file)).map_err(|e| e.to_string())?;
-        Ok(())
-    }
-
-    fn draw_title(&self, layer: &PdfLayerReference, font: &IndirectFontRef) {
-        layer.use_text(&self.title, 18.0, Mm(MARGIN_MM), Mm(PAGE_HEIGHT_MM - MARGIN_MM), font);
+impl PdfRenderer {
+    pub fn new(title: &str, page: PageFormat, fonts: &FontSet, seed: Option<u64>) -> Result<Self, String> {
+        let (doc, page1, layer1) = PdfDocument::new(title, Mm(page.width_mm), Mm(page.height_mm), "Layer 1");
+        let doc = match seed {
+            Some(seed) => doc
+                .with_subject(format!("Seed: {}", seed))
+                .with_keywords(vec![format!("seed={}", seed)]),
+            None => doc,
+        };
+        let regular = doc.add_external_font(fonts.regular.as_slice()).map_err(|e| e.to_string())?;
+        let bold = doc.add_external_font(fonts.bold.as_slice()).map_err(|e| e.to_string())?;
+        let layers = vec![doc.get_page(page1).get_layer(layer1)];
+        Ok(Self { doc, page, layers, regular, bold })
     }
+}
 
-    fn draw_title_answer(&self, layer: &PdfLayerReference, font: &IndirectFontRef) {
-        let title = format!("{} - GABARITO", self.title);
-        layer.use_text(&title, 18.0, Mm(MARGIN_MM), Mm(PAGE_HEIGHT_MM - MARGIN_MM), font);
+impl Renderer for PdfRenderer {
+    fn add_page(&mut self) -> usize {
+        let (page, layer) = self.doc.add_page(Mm(self.page.width_mm), Mm(self.page.height_mm), "Layer 1");
+        self.layers.push(self.doc.get_page(page).get_layer(layer));
+        self.layers.len() - 1
     }
 
-    fn draw_grid(&self, layer: &PdfLayerReference, grid: &Grid, font: &IndirectFontRef, highlights: Option<&[PlacementResult]>) {
-        let start_x = MARGIN_MM;
-        let start_y = PAGE_HEIGHT_MM - MARGIN_MM - 15.0;
-        let highlighted_cells = self.get_highlighted_cells(grid, highlights);
-
-        for (row_idx, row) in grid.cells.iter().enumerate() {
-            for (col_idx, cell) in row.iter().enumerate() {
-                let x = start_x + (col_idx as f32 * CELL_SIZE_MM);
-                let y = start_y - (row_idx as f32 * CELL_SIZE_MM);
-
-                if highlighted_cells.contains(&(row_idx, col_idx)) {
-                    self.draw_highlight(layer, x, y);
-                }
-
-                let ch = cell.unwrap_or(' ');
-                layer.use_text(&ch.to_string(), 12.0, Mm(x + 2.5), Mm(y - 5.5), font);
-            }
-        }
-
-        self.draw_grid_border(layer, grid.size, start_x, start_y);
+    fn text(&mut self, page: usize, text: &str, x: f32, y: f32, size_pt: f32, style: FontStyle) {
+        let font = match style {
+            FontStyle::Regular => &self.regular,
+            FontStyle::Bold => &self.bold,
+        };
+        self.layers[page].use_text(text, size_pt, Mm(x), Mm(y), font);
     }
 
-    fn get_highlighted_cells(&self, grid: &Grid, highlights: Option<&[PlacementResult]>) -> Vec<(usize, usize)> {
-        let mut cells = Vec::new();
-        if let Some(placements) = highlights {
-            for placement in placements {
-                let (dr, dc) = placement.direction.deltas();
-                for i in 0..placement.word.len() {
-                    let row = (placement.row as i32 + dr * i as i32) as usize;
-                    let col = (placement.col as i32 + dc * i as i32) as usize;
-                    if row < grid.size && col < grid.size {
-                        cells.push((row, col));
-                    }
-                }
-            }
-        }
-        cells
+    fn stroke(&mut self, page: usize, points: &[(f32, f32)], closed: bool, stroke: &Stroke) {
+        let layer = &self.layers[page];
+        layer.set_outline_color(pdf_color(stroke.color));
+        layer.set_outline_thickness(stroke.width_mm / PT_TO_MM);
+        let dash = match stroke.dash {
+            Some((dash, gap)) => LineDashPattern {
+                dash_1: Some((dash / PT_TO_MM).round().max(1.0) as i64),
+                gap_1: Some((gap / PT_TO_MM).round().max(1.0) as i64),
+                ..Default::default()
+            },
+            None => LineDashPattern::default(),
+        };
+        layer.set_line_dash_pattern(dash);
+        layer.add_line(Line {
+            points: points.iter().map(|&(x, y)| (Point::new(Mm(x), Mm(y)), false)).collect(),
+            is_closed: closed,
+        });
     }
 
-    fn draw_highlight(&self, layer: &PdfLayerReference, x: f32, y: f32) {
-        let rect = Rect::new(Mm(x), Mm(y - CELL_SIZE_MM + 1.0), Mm(x + CELL_SIZE_MM), Mm(y + 1.0));
-        layer.set_fill_color(Color::Rgb(Rgb::new(1.0, 1.0, 0.0, None)));
-        layer.add_rect(rect);
-        layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+    fn save(self: Box<Self>, path: &Path) -> Result<Vec<PathBuf>, String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        self.doc.save(&mut BufWriter::new(file)).map_err(|e| e.to_string())?;
+        Ok(vec![path.to_path_buf()])
     }
+}
 
-    fn draw_grid_border(&self, layer: &PdfLayerReference, size: usize, start_x: f32, start_y: f32) {
-        let grid_width = size as f32 * CELL_SIZE_MM;
-        let grid_height = size as f32 * CELL_SIZE_MM;
-
-        layer.set_outline_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
-        layer.set_outline_thickness(0.5);
-
-        for i in 0..=size {
-            let x = start_x + (i as f32 * CELL_SIZE_MM);
-            let line = Line {
-                points: vec![
-                    (Point::new(Mm(x), Mm(start_y + 1.0)), false),
-                    (Point::new(Mm(x), Mm(start_y - grid_height + 1.0)), false),
-                ],
-                is_closed: false,
-            };
-            layer.add_line(line);
-        }
-
-        for i in 0..=size {
-            let y = start_y - (i as f32 * CELL_SIZE_MM) + 1.0;
-            let line = Line {
-                points: vec![
-                    (Point::new(Mm(start_x), Mm(y)), false),
-                    (Point::new(Mm(start_x + grid_width), Mm(y)), false),
-                ],
-                is_closed: false,
-            };
-            layer.add_line(line);
-        }
+fn pdf_color(color: render::Color) -> Color {
+    match color {
+        render::Color::Rgb(r, g, b) => Color::Rgb(Rgb::new(r, g, b, None)),
+        render::Color::Gray(level) => Color::Greyscale(Greyscale::new(level, None)),
     }
+}
 
-    fn draw_word_list(&self, layer: &PdfLayerReference, grid: &Grid, words: &[String], font: &IndirectFontRef) {
-        let grid_height = grid.size as f32 * CELL_SIZE_MM;
-        let start_y = PAGE_HEIGHT_MM - MARGIN_MM - 15.0 - grid_height - 15.0;
-        let cols = 3;
-        let col_width = (PAGE_WIDTH_MM - 2.0 * MARGIN_MM) / cols as f32;
-
-        layer.use_text("Palavras para encontrar:", 10.0, Mm(MARGIN_MM), Mm(start_y), font);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        for (i, word) in words.iter().enumerate() {
-            let col = i % cols;
-            let row = i / cols;
-            let x = MARGIN_MM + (col as f32 * col_width);
-            let y = start_y - 8.0 - (row as f32 * 6.0);
-            if y > MARGIN_MM {
-                layer.use_text(word, 10.0, Mm(x), Mm(y), font);
-            }
-        }
+    fn contains(haystack: &[u8], needle: &str) -> bool {
+        haystack.windows(needle.len()).any(|window| window == needle.as_bytes())
     }
 
-    fn draw_placed_words_list(&self, layer: &PdfLayerReference, grid: &Grid, placed_words: &[PlacementResult], font: &IndirectFontRef) {
-        let grid_height = grid.size as f32 * CELL_SIZE_MM;
-        let start_y = PAGE_HEIGHT_MM - MARGIN_MM - 15.0 - grid_height - 15.0;
-        let cols = 3;
-        let col_width = (PAGE_WIDTH_MM - 2.0 * MARGIN_MM) / cols as f32;
-
-        layer.use_text("Palavras encontradas:", 10.0, Mm(MARGIN_MM), Mm(start_y), font);
-
-        for (i, placement) in placed_words.iter().enumerate() {
-            let col = i % cols;
-            let row = i / cols;
-            let x = MARGIN_MM + (col as f32 * col_width);
-            let y = start_y - 8.0 - (row as f32 * 6.0);
-            if y > MARGIN_MM {
-                let text = format!("{} ({},{})", placement.word.original, placement.row, placement.col);
-                layer.use_text(&text, 10.0, Mm(x), Mm(y), font);
-            }
-        }
+    #[test]
+    fn embeds_the_fonts_and_records_the_seed_in_one_file() {
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-{}.pdf", std::process::id()));
+        let mut renderer = PdfRenderer::new("Teste", PageFormat::default(), &FontSet::bundled(), Some(42)).unwrap();
+        let second = renderer.add_page();
+        renderer.text(second, "MAÇÃ", 20.0, 20.0, 12.0, FontStyle::Bold);
+
+        let files = Box::new(renderer).save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(files, vec![path]);
+        assert!(bytes.starts_with(b"%PDF"));
+        assert!(contains(&bytes, "/FontFile2"));
+        assert!(contains(&bytes, "seed=42"));
     }
 }
@@ -0,0 +1,521 @@
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::direction::Direction;
+use crate::hint::{self, HintKind};
+use crate::puzzle::PuzzleFile;
+
+pub const SESSION_VERSION: u32 = 1;
+
+const POINTS_PER_LETTER: u32 = 10;
+const WRONG_COST: u32 = 5;
+const TICK: Duration = Duration::from_millis(250);
+const GRID_TOP: u16 = 3;
+const GRID_LEFT: u16 = 2;
+
+const WORD_COLORS: [Color; 6] = [Color::Green, Color::Cyan, Color::Magenta, Color::Blue, Color::Red, Color::DarkYellow];
+
+/// Progresso de uma partida. O puzzle vai junto, para que a sessão possa ser retomada
+/// mesmo que o `puzzle.json` original tenha sido apagado.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlaySession {
+    pub version: u32,
+    pub puzzle: PuzzleFile,
+    /// Índices de `puzzle.placements` já encontrados, na ordem em que foram achados.
+    pub found: Vec<usize>,
+    pub hints: Vec<HintUse>,
+    pub wrong_attempts: u32,
+    pub elapsed_secs: u64,
+    pub cursor: (usize, usize),
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct HintUse {
+    pub word: usize,
+    pub kind: HintKind,
+}
+
+pub enum PlayOutcome {
+    Completed,
+    Paused,
+}
+
+impl PlaySession {
+    pub fn new(puzzle: PuzzleFile) -> Self {
+        Self {
+            version: SESSION_VERSION,
+            puzzle,
+            found: Vec::new(),
+            hints: Vec::new(),
+            wrong_attempts: 0,
+            elapsed_secs: 0,
+            cursor: (0, 0),
+        }
+    }
+
+    /// Abre uma sessão salva ou, se o arquivo for um puzzle, começa uma partida nova.
+    /// Retorna também se a sessão foi retomada.
+    pub fn open(path: &Path) -> io::Result<(Self, bool)> {
+        let content = fs::read_to_string(path)?;
+        if let Ok(session) = serde_json::from_str::<Self>(&content) {
+            if session.version > SESSION_VERSION {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("versão {} da sessão não suportada (máximo {})", session.version, SESSION_VERSION),
+                ));
+            }
+            session.validate().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            return Ok((session, true));
+        }
+        Ok((Self::new(PuzzleFile::load(path)?), false))
+    }
+
+    /// Confere o puzzle embutido e os índices de palavras achadas e dicas, que num arquivo
+    /// editado à mão podem apontar para palavras ou células que não existem.
+    fn validate(&self) -> Result<(), String> {
+        self.puzzle.validate()?;
+        let words = self.puzzle.placements.len();
+        for (position, &index) in self.found.iter().enumerate() {
+            if index >= words {
+                return Err(format!("palavra achada {} não existe no puzzle ({} palavras)", index, words));
+            }
+            if self.found[..position].contains(&index) {
+                return Err(format!("palavra achada {} aparece mais de uma vez", index));
+            }
+        }
+        if let Some(hint) = self.hints.iter().find(|h| h.word >= words) {
+            return Err(format!("dica para a palavra {}, que não existe no puzzle ({} palavras)", hint.word, words));
+        }
+        let (row, col) = self.cursor;
+        if row >= self.puzzle.grid.height || col >= self.puzzle.grid.width {
+            return Err(format!("cursor ({}, {}) fora do grid", row, col));
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.found.len() == self.puzzle.placements.len()
+    }
+
+    pub fn score(&self) -> u32 {
+        let earned = self
+            .found
+            .iter()
+            .map(|&i| (self.puzzle.placements[i].word.len() as u32).saturating_mul(POINTS_PER_LETTER))
+            .fold(0u32, u32::saturating_add);
+        let hint_cost = self.hints.iter().map(|h| h.kind.cost()).fold(0u32, u32::saturating_add);
+        earned.saturating_sub(hint_cost.saturating_add(self.wrong_attempts.saturating_mul(WRONG_COST)))
+    }
+
+    /// Confere a seleção contra as palavras posicionadas, aceitando-a nos dois sentidos.
+    fn check_selection(&self, start: (usize, usize), end: (usize, usize)) -> Selection {
+        let dr = end.0 as i32 - start.0 as i32;
+        let dc = end.1 as i32 - start.1 as i32;
+        if start == end || (dr != 0 && dc != 0 && dr.abs() != dc.abs()) {
+            return Selection::NotALine;
+        }
+        let Some(direction) = Direction::from_deltas(dr.signum(), dc.signum()) else {
+            return Selection::NotALine;
+        };
+        let reverse = Direction::from_deltas(-dr.signum(), -dc.signum());
+        let allowed = self.puzzle.difficulty.allowed_directions();
+        if !allowed.contains(&direction) && !reverse.is_some_and(|d| allowed.contains(&d)) {
+            return Selection::DirectionNotUsed;
+        }
+
+        for (index, placement) in self.puzzle.placements.iter().enumerate() {
+            let positions = placement.positions();
+            let (first, last) = (positions[0], positions[positions.len() - 1]);
+            if (first, last) == (start, end) || (first, last) == (end, start) {
+                return match self.found.contains(&index) {
+                    true => Selection::AlreadyFound(index),
+                    false => Selection::Found(index),
+                };
+            }
+        }
+        Selection::Wrong
+    }
+
+    fn hints_for(&self, word: usize) -> Vec<HintKind> {
+        self.hints.iter().filter(|h| h.word == word).map(|h| h.kind).collect()
+    }
+
+    /// Continua a progressão da última palavra que recebeu dica; quando ela se esgota,
+    /// passa para a próxima palavra ainda não encontrada.
+    fn next_hint(&self) -> Option<HintUse> {
+        let pending = |word: usize| match self.found.contains(&word) {
+            true => None,
+            false => HintKind::next(&self.hints_for(word)).map(|kind| HintUse { word, kind }),
+        };
+        self.hints
+            .last()
+            .and_then(|last| pending(last.word))
+            .or_else(|| (0..self.puzzle.placements.len()).find_map(pending))
+    }
+}
+
+enum Selection {
+    Found(usize),
+    AlreadyFound(usize),
+    Wrong,
+    NotALine,
+    DirectionNotUsed,
+}
+
+struct Game<'a> {
+    session: PlaySession,
+    session_path: &'a Path,
+    started: Instant,
+    start_cell: Option<(usize, usize)>,
+    message: String,
+}
+
+/// Restaura o terminal mesmo quando o jogo termina com erro.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Roda a partida até o jogador sair ou encontrar todas as palavras. Ao sair antes do fim,
+/// a sessão é gravada em `session_path`.
+pub fn run(session: PlaySession, session_path: &Path) -> io::Result<(PlaySession, PlayOutcome)> {
+    let mut game = Game {
+        session,
+        session_path,
+        started: Instant::now(),
+        start_cell: None,
+        message: "Mova o cursor até a primeira letra de uma palavra e aperte espaço.".to_string(),
+    };
+
+    let outcome = {
+        let _guard = TerminalGuard::enter()?;
+        game.event_loop()?
+    };
+
+    if let PlayOutcome::Paused = outcome {
+        game.session.elapsed_secs = game.elapsed();
+        game.session.save(session_path)?;
+    }
+    Ok((game.session, outcome))
+}
+
+impl Game<'_> {
+    fn elapsed(&self) -> u64 {
+        self.session.elapsed_secs.saturating_add(self.started.elapsed().as_secs())
+    }
+
+    fn event_loop(&mut self) -> io::Result<PlayOutcome> {
+        let mut out = io::stdout();
+        loop {
+            self.draw(&mut out)?;
+            if !event::poll(TICK)? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            if self.session.is_complete() {
+                return Ok(PlayOutcome::Completed);
+            }
+
+            let (row, col) = self.session.cursor;
+            let grid = &self.session.puzzle.grid;
+            match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(PlayOutcome::Paused),
+                KeyCode::Char('q') => return Ok(PlayOutcome::Paused),
+                KeyCode::Up | KeyCode::Char('k') => self.session.cursor = (row.saturating_sub(1), col),
+                KeyCode::Down | KeyCode::Char('j') => self.session.cursor = ((row + 1).min(grid.height - 1), col),
+                KeyCode::Left | KeyCode::Char('h') => self.session.cursor = (row, col.saturating_sub(1)),
+                KeyCode::Right | KeyCode::Char('l') => self.session.cursor = (row, (col + 1).min(grid.width - 1)),
+                KeyCode::Char(' ') | KeyCode::Enter => self.mark(),
+                KeyCode::Esc => {
+                    self.start_cell = None;
+                    self.message = "Seleção cancelada.".to_string();
+                }
+                KeyCode::Char('d') => self.hint(),
+                KeyCode::Char('g') => {
+                    self.session.elapsed_secs = self.elapsed();
+                    self.started = Instant::now();
+                    self.message = match self.session.save(self.session_path) {
+                        Ok(()) => format!("Sessão salva em {}", self.session_path.display()),
+                        Err(e) => format!("Erro ao salvar a sessão: {}", e),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn mark(&mut self) {
+        let cursor = self.session.cursor;
+        if !self.session.puzzle.grid.is_active(cursor.0, cursor.1) {
+            self.message = "Esta célula está fora do grid.".to_string();
+            return;
+        }
+        let Some(start) = self.start_cell.take() else {
+            self.start_cell = Some(cursor);
+            self.message = "Agora marque a última letra (Esc cancela).".to_string();
+            return;
+        };
+
+        self.message = match self.session.check_selection(start, cursor) {
+            Selection::Found(index) => {
+                self.session.found.push(index);
+                let word = &self.session.puzzle.placements[index].word.original;
+                if self.session.is_complete() {
+                    self.session.elapsed_secs = self.elapsed();
+                    self.started = Instant::now();
+                    format!("Parabéns! Você encontrou todas as palavras. Aperte qualquer tecla para sair. ({})", word)
+                } else {
+                    format!("✓ {}!", word)
+                }
+            }
+            Selection::AlreadyFound(index) => {
+                format!("{} já foi encontrada.", self.session.puzzle.placements[index].word.original)
+            }
+            Selection::Wrong => {
+                self.session.wrong_attempts = self.session.wrong_attempts.saturating_add(1);
+                format!("Nenhuma palavra aqui (−{} pontos).", WRONG_COST)
+            }
+            Selection::NotALine => "A seleção precisa ser uma linha reta com pelo menos duas letras.".to_string(),
+            Selection::DirectionNotUsed => "Este puzzle não usa palavras nessa direção.".to_string(),
+        };
+    }
+
+    fn hint(&mut self) {
+        let Some(next) = self.session.next_hint() else {
+            self.message = "Não há mais dicas disponíveis.".to_string();
+            return;
+        };
+        self.session.hints.push(next);
+        let placement = &self.session.puzzle.placements[next.word];
+        let hint = hint::hint(&self.session.puzzle.grid, placement, next.kind);
+        self.message = format!("Dica: {} {} (−{} pontos).", placement.word.original, hint.text, next.kind.cost());
+    }
+
+    /// Células entre a primeira letra marcada e o cursor, se formarem uma linha reta.
+    fn preview(&self) -> Vec<(usize, usize)> {
+        let Some(start) = self.start_cell else {
+            return Vec::new();
+        };
+        let end = self.session.cursor;
+        let dr = end.0 as i32 - start.0 as i32;
+        let dc = end.1 as i32 - start.1 as i32;
+        if dr != 0 && dc != 0 && dr.abs() != dc.abs() {
+            return vec![start];
+        }
+        (0..=dr.abs().max(dc.abs()))
+            .map(|i| ((start.0 as i32 + dr.signum() * i) as usize, (start.1 as i32 + dc.signum() * i) as usize))
+            .collect()
+    }
+
+    fn draw(&self, out: &mut impl Write) -> io::Result<()> {
+        let session = &self.session;
+        let puzzle = &session.puzzle;
+        let grid = &puzzle.grid;
+        let elapsed = if session.is_complete() { session.elapsed_secs } else { self.elapsed() };
+
+        queue!(out, Clear(ClearType::All), MoveTo(GRID_LEFT, 0), SetAttribute(Attribute::Bold), Print(&puzzle.title), SetAttribute(Attribute::Reset))?;
+        queue!(
+            out,
+            MoveTo(GRID_LEFT, 1),
+            Print(format!(
+                "Tempo {}   Pontos {}   Dicas {}   {}/{} palavras",
+                clock(elapsed),
+                session.score(),
+                session.hints.len(),
+                session.found.len(),
+                puzzle.placements.len()
+            ))
+        )?;
+
+        let preview = self.preview();
+        for row in 0..grid.height {
+            queue!(out, MoveTo(GRID_LEFT, GRID_TOP + row as u16))?;
+            for col in 0..grid.width {
+                let letter = match grid.get(row, col) {
+                    Some(ch) if grid.is_active(row, col) => ch,
+                    _ => ' ',
+                };
+                let found_by = session
+                    .found
+                    .iter()
+                    .rev()
+                    .find(|&&i| puzzle.placements[i].positions().contains(&(row, col)));
+                let hinted = session
+                    .hints
+                    .iter()
+                    .filter(|h| h.kind == HintKind::StartCell && !session.found.contains(&h.word))
+                    .any(|h| (puzzle.placements[h.word].row, puzzle.placements[h.word].col) == (row, col));
+
+                if let Some(&index) = found_by {
+                    queue!(out, SetForegroundColor(WORD_COLORS[index % WORD_COLORS.len()]), SetAttribute(Attribute::Bold))?;
+                }
+                if hinted {
+                    queue!(out, SetForegroundColor(Color::Yellow), SetAttribute(Attribute::Underlined))?;
+                }
+                if preview.contains(&(row, col)) {
+                    queue!(out, SetBackgroundColor(Color::DarkGrey))?;
+                }
+                if session.cursor == (row, col) {
+                    queue!(out, SetAttribute(Attribute::Reverse))?;
+                }
+                queue!(out, Print(letter), SetAttribute(Attribute::Reset), ResetColor, Print(' '))?;
+            }
+        }
+
+        let list_left = GRID_LEFT + grid.width as u16 * 2 + 4;
+        queue!(out, MoveTo(list_left, GRID_TOP - 1), Print("Palavras:"))?;
+        let rows = grid.height.max(1);
+        let notes: Vec<String> = (0..puzzle.placements.len())
+            .map(|i| match session.found.contains(&i) {
+                true => String::new(),
+                false => session
+                    .hints_for(i)
+                    .into_iter()
+                    .map(|kind| format!(" {}", hint::hint(grid, &puzzle.placements[i], kind).short))
+                    .collect(),
+            })
+            .collect();
+        let column_width = puzzle
+            .placements
+            .iter()
+            .zip(&notes)
+            .map(|(p, note)| p.word.original.chars().count() + note.chars().count())
+            .max()
+            .unwrap_or(0) as u16
+            + 4;
+        for (i, (placement, note)) in puzzle.placements.iter().zip(&notes).enumerate() {
+            let x = list_left + (i / rows) as u16 * column_width;
+            let y = GRID_TOP + (i % rows) as u16;
+            queue!(out, MoveTo(x, y))?;
+            if session.found.contains(&i) {
+                queue!(out, SetForegroundColor(WORD_COLORS[i % WORD_COLORS.len()]), SetAttribute(Attribute::CrossedOut))?;
+            }
+            queue!(
+                out,
+                Print(&placement.word.original),
+                SetAttribute(Attribute::Reset),
+                SetForegroundColor(Color::Yellow),
+                Print(note),
+                ResetColor
+            )?;
+        }
+
+        let bottom = GRID_TOP + grid.height as u16 + 1;
+        queue!(
+            out,
+            MoveTo(GRID_LEFT, bottom),
+            Print(&self.message),
+            MoveTo(GRID_LEFT, bottom + 2),
+            SetForegroundColor(Color::DarkGrey),
+            Print("setas/hjkl mover · espaço marcar · Esc cancelar · d dica · g salvar · q sair"),
+            ResetColor
+        )?;
+        out.flush()
+    }
+}
+
+pub fn clock(seconds: u64) -> String {
+    format!("{:02}:{:02}", seconds / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+    use crate::grid::Grid;
+    use crate::word::Word;
+
+    fn session() -> PlaySession {
+        let mut grid = Grid::new(6, 6);
+        let pizza = grid.place_word(&Word::new("PIZZA"), 0, 0, Direction::Horizontal);
+        let forno = grid.place_word(&Word::new("FORNO"), 1, 5, Direction::Vertical);
+        let words = vec!["PIZZA".to_string(), "FORNO".to_string()];
+        PlaySession::new(PuzzleFile::new("Teste", 1, Difficulty::easy(), words, grid, vec![pizza, forno]))
+    }
+
+    #[test]
+    fn selections_match_in_both_senses() {
+        let mut session = session();
+        assert!(matches!(session.check_selection((0, 0), (0, 4)), Selection::Found(0)));
+        assert!(matches!(session.check_selection((5, 5), (1, 5)), Selection::Found(1)));
+        session.found.push(0);
+        assert!(matches!(session.check_selection((0, 4), (0, 0)), Selection::AlreadyFound(0)));
+        assert!(matches!(session.check_selection((0, 0), (0, 3)), Selection::Wrong));
+        assert!(matches!(session.check_selection((0, 0), (2, 1)), Selection::NotALine));
+        assert!(matches!(session.check_selection((0, 0), (4, 4)), Selection::DirectionNotUsed));
+    }
+
+    #[test]
+    fn score_charges_hints_and_mistakes_without_overflowing() {
+        let mut session = session();
+        session.found = vec![0, 1];
+        session.hints.push(HintUse { word: 1, kind: HintKind::Quadrant });
+        session.wrong_attempts = 2;
+        assert_eq!(session.score(), 10 * POINTS_PER_LETTER - HintKind::Quadrant.cost() - 2 * WRONG_COST);
+
+        session.wrong_attempts = u32::MAX;
+        assert_eq!(session.score(), 0);
+    }
+
+    #[test]
+    fn validate_rejects_indexes_outside_the_puzzle() {
+        assert!(session().validate().is_ok());
+
+        let mut bad = session();
+        bad.found = vec![2];
+        assert!(bad.validate().is_err());
+
+        let mut bad = session();
+        bad.found = vec![1, 1];
+        assert!(bad.validate().is_err());
+
+        let mut bad = session();
+        bad.hints.push(HintUse { word: 5, kind: HintKind::StartCell });
+        assert!(bad.validate().is_err());
+
+        let mut bad = session();
+        bad.cursor = (0, 6);
+        assert!(bad.validate().is_err());
+    }
+
+    #[test]
+    fn open_reports_invalid_sessions_as_errors() {
+        let mut bad = session();
+        bad.found = vec![7];
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-sessao-{}.json", std::process::id()));
+        bad.save(&path).unwrap();
+        let result = PlaySession::open(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,192 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use tiny_skia::{FillRule, LineJoin, Paint, PathBuilder, Pixmap, StrokeDash, Transform};
+
+use crate::font::{FontSet, PathOp};
+use crate::render::{page_path, Color, FontStyle, PageFormat, Renderer, Stroke, PT_TO_MM};
+
+const MM_PER_INCH: f32 = 25.4;
+
+pub struct PngRenderer {
+    page: PageFormat,
+    fonts: FontSet,
+    scale: f32,
+    glyphs: HashMap<(FontStyle, char), (Vec<PathOp>, f32)>,
+    pages: Vec<Pixmap>,
+}
+
+impl PngRenderer {
+    pub fn new(page: PageFormat, fonts: &FontSet, dpi: u32) -> Result<Self, String> {
+        let scale = dpi as f32 / MM_PER_INCH;
+        let mut renderer = Self {
+            page,
+            fonts: fonts.clone(),
+            scale,
+            glyphs: HashMap::new(),
+            pages: Vec::new(),
+        };
+        renderer.pages.push(renderer.blank_page()?);
+        Ok(renderer)
+    }
+
+    fn blank_page(&self) -> Result<Pixmap, String> {
+        let width = (self.page.width_mm * self.scale).round() as u32;
+        let height = (self.page.height_mm * self.scale).round() as u32;
+        let mut pixmap = Pixmap::new(width, height)
+            .ok_or_else(|| format!("não foi possível criar uma imagem de {}x{} pixels", width, height))?;
+        pixmap.fill(tiny_skia::Color::WHITE);
+        Ok(pixmap)
+    }
+
+    fn to_pixels(&self, x: f32, y: f32) -> (f32, f32) {
+        (x * self.scale, (self.page.height_mm - y) * self.scale)
+    }
+}
+
+impl Renderer for PngRenderer {
+    fn add_page(&mut self) -> usize {
+        let pixmap = self
+            .blank_page()
+            .unwrap_or_else(|_| Pixmap::new(1, 1).expect("imagem de 1x1 pixel"));
+        self.pages.push(pixmap);
+        self.pages.len() - 1
+    }
+
+    fn text(&mut self, page: usize, text: &str, x: f32, y: f32, size_pt: f32, style: FontStyle) {
+        let em = size_pt * PT_TO_MM;
+        let mut pen = x;
+        let mut builder = PathBuilder::new();
+
+        for ch in text.chars() {
+            if !self.glyphs.contains_key(&(style, ch)) {
+                let Some(glyph) = self.fonts.glyph_outline(style, ch) else {
+                    continue;
+                };
+                self.glyphs.insert((style, ch), glyph);
+            }
+            let (ops, advance) = &self.glyphs[&(style, ch)];
+            let point = |gx: f32, gy: f32| self.to_pixels(pen + gx * em, y + gy * em);
+            for op in ops {
+                match *op {
+                    PathOp::MoveTo(gx, gy) => {
+                        let (px, py) = point(gx, gy);
+                        builder.move_to(px, py);
+                    }
+                    PathOp::LineTo(gx, gy) => {
+                        let (px, py) = point(gx, gy);
+                        builder.line_to(px, py);
+                    }
+                    PathOp::QuadTo(cx, cy, gx, gy) => {
+                        let ((c1, c2), (px, py)) = (point(cx, cy), point(gx, gy));
+                        builder.quad_to(c1, c2, px, py);
+                    }
+                    PathOp::CubicTo(x1, y1, x2, y2, gx, gy) => {
+                        let ((a1, a2), (b1, b2), (px, py)) = (point(x1, y1), point(x2, y2), point(gx, gy));
+                        builder.cubic_to(a1, a2, b1, b2, px, py);
+                    }
+                    PathOp::Close => builder.close(),
+                }
+            }
+            pen += advance * em;
+        }
+
+        if let Some(path) = builder.finish() {
+            let paint = paint(Color::Gray(0.0));
+            self.pages[page].fill_path(&path, &paint, FillRule::Winding, Transform::identity(), None);
+        }
+    }
+
+    fn stroke(&mut self, page: usize, points: &[(f32, f32)], closed: bool, stroke: &Stroke) {
+        let mut builder = PathBuilder::new();
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let (px, py) = self.to_pixels(x, y);
+            if i == 0 {
+                builder.move_to(px, py);
+            } else {
+                builder.line_to(px, py);
+            }
+        }
+        if closed {
+            builder.close();
+        }
+        let Some(path) = builder.finish() else {
+            return;
+        };
+
+        let skia_stroke = tiny_skia::Stroke {
+            width: (stroke.width_mm * self.scale).max(1.0),
+            line_join: LineJoin::Round,
+            dash: stroke
+                .dash
+                .and_then(|(dash, gap)| StrokeDash::new(vec![dash * self.scale, gap * self.scale], 0.0)),
+            ..Default::default()
+        };
+        self.pages[page].stroke_path(&path, &paint(stroke.color), &skia_stroke, Transform::identity(), None);
+    }
+
+    fn save(self: Box<Self>, path: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut written = Vec::new();
+        for (index, pixmap) in self.pages.iter().enumerate() {
+            let page_file = page_path(path, index);
+            pixmap.save_png(&page_file).map_err(|e| format!("{}: {}", page_file.display(), e))?;
+            written.push(page_file);
+        }
+        Ok(written)
+    }
+}
+
+fn paint(color: Color) -> Paint<'static> {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (channel(r), channel(g), channel(b)),
+        Color::Gray(level) => (channel(level), channel(level), channel(level)),
+    };
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(r, g, b, 255);
+    paint.anti_alias = true;
+    paint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: PageFormat = PageFormat { width_mm: 50.8, height_mm: 25.4 };
+
+    #[test]
+    fn page_size_follows_the_dpi() {
+        let renderer = PngRenderer::new(PAGE, &FontSet::bundled(), 100).unwrap();
+        assert_eq!((renderer.pages[0].width(), renderer.pages[0].height()), (200, 100));
+        assert!(PngRenderer::new(PageFormat { width_mm: 0.0, height_mm: 10.0 }, &FontSet::bundled(), 100).is_err());
+    }
+
+    #[test]
+    fn draws_text_and_strokes_with_the_y_axis_flipped() {
+        let mut renderer = PngRenderer::new(PAGE, &FontSet::bundled(), 100).unwrap();
+        let red = Stroke { color: Color::Rgb(1.0, 0.0, 0.0), width_mm: 1.0, dash: None };
+        // Linha horizontal a 5 mm da base da página, ou seja, perto do fim da imagem.
+        renderer.stroke(0, &[(0.0, 5.0), (50.8, 5.0)], false, &red);
+        renderer.text(0, "W", 25.0, 15.0, 20.0, FontStyle::Bold);
+
+        let pixel = renderer.pages[0].pixel(100, 80).unwrap();
+        assert_eq!((pixel.red(), pixel.green(), pixel.blue()), (255, 0, 0));
+        let corner = renderer.pages[0].pixel(5, 5).unwrap();
+        assert_eq!((corner.red(), corner.green(), corner.blue()), (255, 255, 255));
+        assert!(renderer.pages[0].pixels().iter().any(|p| p.red() < 128 && p.green() < 128));
+    }
+
+    #[test]
+    fn each_page_is_saved_to_its_own_file() {
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-{}.png", std::process::id()));
+        let mut renderer = PngRenderer::new(PAGE, &FontSet::bundled(), 50).unwrap();
+        renderer.add_page();
+
+        let files = Box::new(renderer).save(&path).unwrap();
+        let sizes: Vec<_> = files.iter().map(|f| Pixmap::load_png(f).map(|p| (p.width(), p.height()))).collect();
+        files.iter().for_each(|f| std::fs::remove_file(f).unwrap());
+
+        assert_eq!(files, vec![path.clone(), page_path(&path, 1)]);
+        assert!(sizes.iter().all(|size| size.as_ref().ok() == Some(&(100, 50))));
+    }
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::difficulty::Difficulty;
+use crate::grid::{Grid, PlacementResult};
+
+/// Versão 2: a dificuldade é um perfil com direções e pesos. Arquivos da versão 1, com os
+/// quatro booleanos, continuam sendo lidos.
+pub const FORMAT_VERSION: u32 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PuzzleFile {
+    pub version: u32,
+    pub title: String,
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub words: Vec<String>,
+    pub grid: Grid,
+    pub placements: Vec<PlacementResult>,
+    /// Mensagem secreta formada pelas letras que sobram, quando houver.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl PuzzleFile {
+    pub fn new(
+        title: &str,
+        seed: u64,
+        difficulty: Difficulty,
+        words: Vec<String>,
+        grid: Grid,
+        placements: Vec<PlacementResult>,
+    ) -> Self {
+        Self {
+            version: FORMAT_VERSION,
+            title: title.to_string(),
+            seed,
+            difficulty,
+            words,
+            grid,
+            placements,
+            message: None,
+        }
+    }
+
+    pub fn with_message(mut self, message: Option<String>) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        fs::write(path, json)
+    }
+
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        let puzzle: Self = serde_json::from_str(&content)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        if puzzle.version > FORMAT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("versão {} do arquivo não suportada (máximo {})", puzzle.version, FORMAT_VERSION),
+            ));
+        }
+
+        puzzle.validate().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        Ok(puzzle)
+    }
+
+    /// Confere se cada palavra posicionada tem letras e cabe inteira no grid, para que um
+    /// arquivo editado à mão vire um erro de leitura e não um pânico mais adiante.
+    pub fn validate(&self) -> Result<(), String> {
+        for (index, placement) in self.placements.iter().enumerate() {
+            let len = placement.word.len();
+            if len == 0 {
+                return Err(format!("a palavra posicionada {} está vazia", index + 1));
+            }
+            if !placement.word.normalized.bytes().all(|b| b.is_ascii_uppercase()) {
+                return Err(format!("a palavra {} tem letras fora de A-Z", placement.word.original));
+            }
+            let (dr, dc) = placement.direction.deltas();
+            let steps = len as i64 - 1;
+            let end_row = placement.row as i64 + dr as i64 * steps;
+            let end_col = placement.col as i64 + dc as i64 * steps;
+            let inside = |row: i64, col: i64| {
+                (0..self.grid.height as i64).contains(&row) && (0..self.grid.width as i64).contains(&col)
+            };
+            if !inside(placement.row as i64, placement.col as i64) || !inside(end_row, end_col) {
+                return Err(format!("a palavra {} sai dos limites do grid", placement.word.original));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+    use crate::word::Word;
+
+    fn puzzle(word: Word, row: usize, col: usize) -> PuzzleFile {
+        let mut grid = Grid::new(5, 5);
+        grid.place_word(&Word::new("PIZZA"), 0, 0, Direction::Horizontal);
+        grid.place_word(&Word::new("QUEIJ"), 1, 0, Direction::Horizontal);
+        let placement = PlacementResult { word, row, col, direction: Direction::Horizontal };
+        PuzzleFile::new("Teste", 1, Difficulty::easy(), vec!["PIZZA".to_string()], grid, vec![placement])
+    }
+
+    #[test]
+    fn accepts_placements_inside_the_grid() {
+        assert!(puzzle(Word::new("PIZZA"), 0, 0).validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_empty_foreign_or_overflowing_placements() {
+        assert!(puzzle(Word::new(""), 0, 0).validate().is_err());
+        let foreign = Word { original: "PIÇZA".to_string(), normalized: "PIÇZA".to_string() };
+        assert!(puzzle(foreign, 0, 0).validate().is_err());
+        assert!(puzzle(Word::new("PIZZA"), 0, 1).validate().is_err());
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("pizza-wordsearch-{}-{}.json", name, std::process::id()))
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let original = puzzle(Word::new("PIZZA"), 0, 0).with_message(Some("OI".to_string()));
+        let path = temp_path("ida-e-volta");
+        original.save(&path).unwrap();
+        let loaded = PuzzleFile::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        let loaded = loaded.unwrap();
+        assert_eq!(loaded.version, FORMAT_VERSION);
+        assert_eq!(loaded.message.as_deref(), Some("OI"));
+        assert_eq!(serde_json::to_string(&loaded.grid).unwrap(), serde_json::to_string(&original.grid).unwrap());
+        assert_eq!(loaded.placements[0].word.normalized, "PIZZA");
+    }
+
+    #[test]
+    fn load_rejects_newer_versions() {
+        let mut newer = puzzle(Word::new("PIZZA"), 0, 0);
+        newer.version = FORMAT_VERSION + 1;
+        let path = temp_path("versao");
+        newer.save(&path).unwrap();
+        let result = PuzzleFile::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(result.unwrap_err().to_string().contains("não suportada"));
+    }
+
+    #[test]
+    fn load_rejects_invalid_grid_letters() {
+        let json = serde_json::to_string(&puzzle(Word::new("PIZZA"), 0, 0)).unwrap().replace("QUEIJ", "QUEIÇ");
+        let path = temp_path("letras");
+        fs::write(&path, json).unwrap();
+        let result = PuzzleFile::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,971 @@
+use std::path::{Path, PathBuf};
+
+use crate::font::FontSet;
+use crate::grid::{Grid, PlacementResult};
+use crate::hint;
+use crate::pdf::PdfRenderer;
+use crate::png::PngRenderer;
+use crate::puzzle::PuzzleFile;
+use crate::svg::SvgRenderer;
+
+pub const PT_TO_MM: f32 = 0.3528;
+
+const MARGIN_MM: f32 = 20.0;
+const HEADER_HEIGHT_MM: f32 = 15.0;
+const MAX_CELL_SIZE_MM: f32 = 10.0;
+const MIN_CELL_SIZE_MM: f32 = 4.0;
+const MIN_LIST_SPACE_MM: f32 = 30.0;
+const CELL_FONT_RATIO: f32 = 1.5;
+const LIST_COLUMNS: usize = 3;
+const LIST_FONT_SIZE: f32 = 10.0;
+const LIST_LINE_HEIGHT_MM: f32 = 6.0;
+const FOOTER_FONT_SIZE: f32 = 8.0;
+const HEADING_FONT_SIZE: f32 = 18.0;
+const TOC_LINE_HEIGHT_MM: f32 = 8.0;
+const THUMBNAIL_TITLE_FONT_SIZE: f32 = 10.0;
+const THUMBNAIL_PADDING_MM: f32 = 4.0;
+const INNER_LINE_MM: f32 = 0.18;
+const OUTLINE_MM: f32 = 0.53;
+const CAPSULE_RADIUS_RATIO: f32 = 0.42;
+const CAPSULE_ARC_STEPS: usize = 12;
+const COORDINATE_SPACE_MM: f32 = 6.0;
+const COORDINATE_GAP_MM: f32 = 1.5;
+const MIN_GRID_SPACE_MM: f32 = 40.0;
+
+/// Menor lado de página aceito, valendo nas duas orientações: margens, cabeçalho, lista,
+/// números das coordenadas e pelo menos `MIN_GRID_SPACE_MM` para o grid.
+pub const MIN_PAGE_SIDE_MM: f32 =
+    2.0 * MARGIN_MM + HEADER_HEIGHT_MM + MIN_LIST_SPACE_MM + COORDINATE_SPACE_MM + MIN_GRID_SPACE_MM;
+
+const PALETTE: [(f32, f32, f32); 10] = [
+    (0.90, 0.10, 0.10),
+    (0.10, 0.45, 0.85),
+    (0.10, 0.65, 0.25),
+    (0.95, 0.55, 0.05),
+    (0.55, 0.20, 0.75),
+    (0.00, 0.65, 0.70),
+    (0.85, 0.20, 0.60),
+    (0.55, 0.40, 0.15),
+    (0.45, 0.70, 0.05),
+    (0.25, 0.25, 0.60),
+];
+const GRAY_LEVELS: [f32; 3] = [0.0, 0.35, 0.6];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PageSize {
+    A4,
+    A5,
+    Letter,
+    Legal,
+    Custom { width_mm: f32, height_mm: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    Portrait,
+    Landscape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnswerStyle {
+    #[default]
+    Color,
+    Grayscale,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Pdf,
+    Svg,
+    Png,
+    Html,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageFormat {
+    pub width_mm: f32,
+    pub height_mm: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Color {
+    Rgb(f32, f32, f32),
+    Gray(f32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum FontStyle {
+    Regular,
+    Bold,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stroke {
+    pub color: Color,
+    pub width_mm: f32,
+    /// Traço e espaço, em mm; `None` desenha uma linha contínua.
+    pub dash: Option<(f32, f32)>,
+}
+
+/// Destino de desenho de uma página. Coordenadas em mm com origem no canto inferior
+/// esquerdo, como no PDF; o texto é posicionado pela linha de base.
+pub trait Renderer {
+    fn add_page(&mut self) -> usize;
+    fn text(&mut self, page: usize, text: &str, x: f32, y: f32, size_pt: f32, style: FontStyle);
+    fn stroke(&mut self, page: usize, points: &[(f32, f32)], closed: bool, stroke: &Stroke);
+    /// Grava o documento; formatos de uma página por arquivo numeram os arquivos extras.
+    fn save(self: Box<Self>, path: &Path) -> Result<Vec<PathBuf>, String>;
+}
+
+#[derive(Debug, Default)]
+pub struct RenderReport {
+    pub pages: usize,
+    pub warnings: Vec<String>,
+    pub files: Vec<PathBuf>,
+}
+
+impl PageSize {
+    pub fn dimensions(&self) -> (f32, f32) {
+        match *self {
+            PageSize::A4 => (210.0, 297.0),
+            PageSize::A5 => (148.0, 210.0),
+            PageSize::Letter => (215.9, 279.4),
+            PageSize::Legal => (215.9, 355.6),
+            PageSize::Custom { width_mm, height_mm } => (width_mm, height_mm),
+        }
+    }
+}
+
+impl PageFormat {
+    pub fn new(size: PageSize, orientation: Orientation) -> Self {
+        let (short, long) = {
+            let (w, h) = size.dimensions();
+            (w.min(h), w.max(h))
+        };
+        match orientation {
+            Orientation::Portrait => Self { width_mm: short, height_mm: long },
+            Orientation::Landscape => Self { width_mm: long, height_mm: short },
+        }
+    }
+}
+
+impl Default for PageFormat {
+    fn default() -> Self {
+        Self::new(PageSize::A4, Orientation::Portrait)
+    }
+}
+
+impl OutputFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Pdf => "pdf",
+            OutputFormat::Svg => "svg",
+            OutputFormat::Png => "png",
+            OutputFormat::Html => "html",
+        }
+    }
+}
+
+impl Stroke {
+    fn solid(color: Color, width_mm: f32) -> Self {
+        Self { color, width_mm, dash: None }
+    }
+}
+
+const BLACK: Color = Color::Gray(0.0);
+
+struct Area {
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+}
+
+struct GridLayout {
+    left: f32,
+    top: f32,
+    cell: f32,
+    font_size: f32,
+}
+
+impl GridLayout {
+    fn cell_x(&self, col: usize) -> f32 {
+        self.left + col as f32 * self.cell
+    }
+
+    fn cell_top(&self, row: usize) -> f32 {
+        self.top - row as f32 * self.cell
+    }
+
+    fn bottom(&self, grid: &Grid) -> f32 {
+        self.cell_top(grid.height)
+    }
+
+    fn cell_center(&self, row: usize, col: usize) -> (f32, f32) {
+        (self.cell_x(col) + self.cell / 2.0, self.cell_top(row) - self.cell / 2.0)
+    }
+}
+
+struct ListPages<'a> {
+    heading: &'a str,
+    label: &'a str,
+    items: &'a [String],
+    start_y: f32,
+}
+
+/// Monta as páginas do puzzle, do gabarito e do caderno sobre qualquer `Renderer`.
+pub struct PageComposer {
+    title: String,
+    seed: Option<u64>,
+    page: PageFormat,
+    fonts: FontSet,
+    answer_style: AnswerStyle,
+    dpi: u32,
+}
+
+impl PageComposer {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            seed: None,
+            page: PageFormat::default(),
+            fonts: FontSet::default(),
+            answer_style: AnswerStyle::default(),
+            dpi: 150,
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_page(mut self, page: PageFormat) -> Self {
+        self.page = page;
+        self
+    }
+
+    pub fn with_fonts(mut self, fonts: FontSet) -> Self {
+        self.fonts = fonts;
+        self
+    }
+
+    pub fn with_answer_style(mut self, style: AnswerStyle) -> Self {
+        self.answer_style = style;
+        self
+    }
+
+    pub fn with_dpi(mut self, dpi: u32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    pub fn generate_puzzle(&self, format: OutputFormat, grid: &Grid, words: &[String], path: &Path) -> Result<RenderReport, String> {
+        let mut renderer = self.renderer(format, &self.title)?;
+        let mut report = self.compose_puzzle(renderer.as_mut(), grid, words);
+        report.files = renderer.save(path)?;
+        Ok(report)
+    }
+
+    pub fn generate_answer_key(
+        &self,
+        format: OutputFormat,
+        grid: &Grid,
+        placed_words: &[PlacementResult],
+        message: Option<&str>,
+        path: &Path,
+    ) -> Result<RenderReport, String> {
+        let mut renderer = self.renderer(format, &format!("{} - Gabarito", self.title))?;
+        let mut report = self.compose_answer_key(renderer.as_mut(), grid, placed_words, message);
+        report.files = renderer.save(path)?;
+        Ok(report)
+    }
+
+    pub fn generate_hint_sheet(&self, format: OutputFormat, grid: &Grid, placed_words: &[PlacementResult], path: &Path) -> Result<RenderReport, String> {
+        let mut renderer = self.renderer(format, &format!("{} - Dicas", self.title))?;
+        let mut report = self.compose_hint_sheet(renderer.as_mut(), grid, placed_words);
+        report.files = renderer.save(path)?;
+        Ok(report)
+    }
+
+    pub fn generate_booklet(&self, format: OutputFormat, puzzles: &[PuzzleFile], keys_per_page: usize, path: &Path) -> Result<RenderReport, String> {
+        let mut renderer = self.renderer(format, &self.title)?;
+        let mut report = self.compose_booklet(renderer.as_mut(), puzzles, keys_per_page);
+        report.files = renderer.save(path)?;
+        Ok(report)
+    }
+
+    fn renderer(&self, format: OutputFormat, document_title: &str) -> Result<Box<dyn Renderer>, String> {
+        Ok(match format {
+            OutputFormat::Pdf => Box::new(PdfRenderer::new(document_title, self.page, &self.fonts, self.seed)?),
+            OutputFormat::Svg => Box::new(SvgRenderer::new(self.page, &self.fonts)),
+            OutputFormat::Png => Box::new(PngRenderer::new(self.page, &self.fonts, self.dpi)?),
+            OutputFormat::Html => return Err("o formato html não é dividido em páginas".to_string()),
+        })
+    }
+
+    pub fn compose_puzzle(&self, renderer: &mut dyn Renderer, grid: &Grid, words: &[String]) -> RenderReport {
+        let mut warnings = Vec::new();
+
+        self.check_glyphs(words.iter().map(String::as_str).chain([self.title.as_str()]), &mut warnings);
+        self.draw_heading(renderer, 0, &self.title, &mut warnings);
+        let layout = self.grid_layout(grid, &mut warnings);
+        self.draw_grid(renderer, 0, grid, &layout);
+        let list = ListPages {
+            heading: &self.title,
+            label: "Palavras para encontrar:",
+            items: words,
+            start_y: layout.bottom(grid) - HEADER_HEIGHT_MM,
+        };
+        let pages: Vec<_> = self
+            .draw_list(renderer, 0, list, &mut warnings)
+            .into_iter()
+            .map(|page| (page, self.seed))
+            .collect();
+        self.draw_footers(renderer, &pages);
+
+        RenderReport { pages: pages.len(), warnings, files: Vec::new() }
+    }
+
+    pub fn compose_answer_key(&self, renderer: &mut dyn Renderer, grid: &Grid, placed_words: &[PlacementResult], message: Option<&str>) -> RenderReport {
+        let mut warnings = Vec::new();
+
+        let heading = format!("{} - GABARITO", self.title);
+        let message = message.map(|m| format!("Mensagem secreta: {}", m));
+        let originals = placed_words.iter().map(|p| p.word.original.as_str());
+        self.check_glyphs(originals.chain([heading.as_str()]).chain(message.as_deref()), &mut warnings);
+        self.draw_heading(renderer, 0, &heading, &mut warnings);
+        let layout = self.grid_layout(grid, &mut warnings);
+        self.draw_grid(renderer, 0, grid, &layout);
+        self.draw_capsules(renderer, 0, &layout, placed_words);
+        let mut start_y = layout.bottom(grid) - HEADER_HEIGHT_MM;
+        if let Some(message) = &message {
+            if self.text_width(message, LIST_FONT_SIZE) > self.page.width_mm - 2.0 * MARGIN_MM {
+                warnings.push("a mensagem secreta é mais larga que a página e pode ser cortada".to_string());
+            }
+            renderer.text(0, message, MARGIN_MM, start_y, LIST_FONT_SIZE, FontStyle::Bold);
+            start_y -= 2.0 * LIST_LINE_HEIGHT_MM;
+        }
+        let items: Vec<String> = placed_words
+            .iter()
+            .map(|p| {
+                let (row, col) = hint::start_coordinates(p);
+                format!("{} (L{}, C{})", p.word.original, row, col)
+            })
+            .collect();
+        let list = ListPages {
+            heading: &heading,
+            label: "Palavras encontradas:",
+            items: &items,
+            start_y,
+        };
+        let pages: Vec<_> = self
+            .draw_list(renderer, 0, list, &mut warnings)
+            .into_iter()
+            .map(|page| (page, self.seed))
+            .collect();
+        self.draw_footers(renderer, &pages);
+
+        RenderReport { pages: pages.len(), warnings, files: Vec::new() }
+    }
+
+    /// Folha de dicas: o grid com linhas e colunas numeradas e só a posição da primeira letra
+    /// de cada palavra, sem o caminho completo.
+    pub fn compose_hint_sheet(&self, renderer: &mut dyn Renderer, grid: &Grid, placed_words: &[PlacementResult]) -> RenderReport {
+        let mut warnings = Vec::new();
+
+        let heading = format!("{} - Dicas", self.title);
+        let originals = placed_words.iter().map(|p| p.word.original.as_str());
+        self.check_glyphs(originals.chain([heading.as_str()]), &mut warnings);
+        self.draw_heading(renderer, 0, &heading, &mut warnings);
+        let layout = self.coordinate_grid_layout(grid, &mut warnings);
+        self.draw_grid(renderer, 0, grid, &layout);
+        self.draw_coordinates(renderer, 0, grid, &layout);
+        let items: Vec<String> = placed_words
+            .iter()
+            .map(|p| {
+                let (row, col) = hint::start_coordinates(p);
+                format!("{} (L{}, C{})", p.word.original, row, col)
+            })
+            .collect();
+        let list = ListPages {
+            heading: &heading,
+            label: "Primeira letra (L = linha, C = coluna):",
+            items: &items,
+            start_y: layout.bottom(grid) - HEADER_HEIGHT_MM,
+        };
+        let pages: Vec<_> = self
+            .draw_list(renderer, 0, list, &mut warnings)
+            .into_iter()
+            .map(|page| (page, self.seed))
+            .collect();
+        self.draw_footers(renderer, &pages);
+
+        RenderReport { pages: pages.len(), warnings, files: Vec::new() }
+    }
+
+    pub fn compose_booklet(&self, renderer: &mut dyn Renderer, puzzles: &[PuzzleFile], keys_per_page: usize) -> RenderReport {
+        let mut warnings = Vec::new();
+
+        let toc_rows = ((self.page.height_mm - 2.0 * MARGIN_MM - HEADER_HEIGHT_MM) / TOC_LINE_HEIGHT_MM) as usize;
+        let toc_page_count = (puzzles.len() + 1).div_ceil(toc_rows.max(1));
+        let mut toc_pages = vec![0];
+        for _ in 1..toc_page_count {
+            toc_pages.push(renderer.add_page());
+        }
+        let mut pages: Vec<(usize, Option<u64>)> = toc_pages.iter().map(|&page| (page, None)).collect();
+        let mut toc = Vec::new();
+
+        for puzzle in puzzles {
+            let page = renderer.add_page();
+            toc.push((puzzle.title.clone(), pages.len() + 1));
+
+            self.check_glyphs(puzzle.words.iter().map(String::as_str).chain([puzzle.title.as_str()]), &mut warnings);
+            self.draw_heading(renderer, page, &puzzle.title, &mut warnings);
+            let layout = self.grid_layout(&puzzle.grid, &mut warnings);
+            self.draw_grid(renderer, page, &puzzle.grid, &layout);
+            let list = ListPages {
+                heading: &puzzle.title,
+                label: "Palavras para encontrar:",
+                items: &puzzle.words,
+                start_y: layout.bottom(&puzzle.grid) - HEADER_HEIGHT_MM,
+            };
+            for page in self.draw_list(renderer, page, list, &mut warnings) {
+                pages.push((page, Some(puzzle.seed)));
+            }
+        }
+
+        toc.push(("Gabaritos".to_string(), pages.len() + 1));
+        let numbered: Vec<(usize, &PuzzleFile)> = puzzles.iter().enumerate().map(|(i, p)| (i + 1, p)).collect();
+        for chunk in numbered.chunks(keys_per_page.max(1)) {
+            let page = renderer.add_page();
+            self.draw_heading(renderer, page, "Gabaritos", &mut warnings);
+            for (slot, (number, puzzle)) in chunk.iter().enumerate() {
+                let area = self.thumbnail_area(slot, keys_per_page);
+                self.draw_thumbnail(renderer, page, &area, *number, puzzle);
+            }
+            pages.push((page, None));
+        }
+
+        self.draw_toc(renderer, &toc_pages, &toc, toc_rows);
+        self.draw_footers(renderer, &pages);
+
+        RenderReport { pages: pages.len(), warnings, files: Vec::new() }
+    }
+
+    fn draw_toc(&self, renderer: &mut dyn Renderer, toc_pages: &[usize], entries: &[(String, usize)], rows_per_page: usize) {
+        let right = self.page.width_mm - MARGIN_MM;
+        let dot_width = self.text_width(".", LIST_FONT_SIZE);
+
+        for (page_index, (&page, chunk)) in toc_pages.iter().zip(entries.chunks(rows_per_page.max(1))).enumerate() {
+            let heading = if page_index == 0 {
+                self.title.clone()
+            } else {
+                format!("{} (continuação)", self.title)
+            };
+            self.draw_heading(renderer, page, &heading, &mut Vec::new());
+            let top = self.page.height_mm - MARGIN_MM - HEADER_HEIGHT_MM;
+            renderer.text(page, "Sumário", MARGIN_MM, top, LIST_FONT_SIZE, FontStyle::Bold);
+
+            for (row, (title, number)) in chunk.iter().enumerate() {
+                let y = top - TOC_LINE_HEIGHT_MM * (row + 1) as f32;
+                let number = number.to_string();
+                let title_width = self.text_width(title, LIST_FONT_SIZE);
+                let number_width = self.text_width(&number, LIST_FONT_SIZE);
+                let gap = right - MARGIN_MM - title_width - number_width - 2.0 * dot_width;
+                let leader = ".".repeat((gap / dot_width).max(0.0) as usize);
+
+                renderer.text(page, title, MARGIN_MM, y, LIST_FONT_SIZE, FontStyle::Regular);
+                renderer.text(page, &leader, MARGIN_MM + title_width + dot_width, y, LIST_FONT_SIZE, FontStyle::Regular);
+                renderer.text(page, &number, right - number_width, y, LIST_FONT_SIZE, FontStyle::Regular);
+            }
+        }
+    }
+
+    fn thumbnail_area(&self, slot: usize, per_page: usize) -> Area {
+        let (columns, rows) = match per_page {
+            1 => (1, 1),
+            2 => (1, 2),
+            4 => (2, 2),
+            6 => (2, 3),
+            _ => (3, 3),
+        };
+        let width = (self.page.width_mm - 2.0 * MARGIN_MM) / columns as f32;
+        let height = (self.page.height_mm - 2.0 * MARGIN_MM - HEADER_HEIGHT_MM) / rows as f32;
+        Area {
+            left: MARGIN_MM + (slot % columns) as f32 * width + THUMBNAIL_PADDING_MM,
+            top: self.page.height_mm - MARGIN_MM - HEADER_HEIGHT_MM - (slot / columns) as f32 * height,
+            width: width - 2.0 * THUMBNAIL_PADDING_MM,
+            height: height - 2.0 * THUMBNAIL_PADDING_MM,
+        }
+    }
+
+    fn draw_thumbnail(&self, renderer: &mut dyn Renderer, page: usize, area: &Area, number: usize, puzzle: &PuzzleFile) {
+        let title = format!("{}. {}", number, puzzle.title);
+        renderer.text(page, &title, area.left, area.top, THUMBNAIL_TITLE_FONT_SIZE, FontStyle::Bold);
+
+        let title_height = THUMBNAIL_TITLE_FONT_SIZE * PT_TO_MM + 2.0;
+        let grid_area = Area {
+            left: area.left,
+            top: area.top - title_height,
+            width: area.width,
+            height: area.height - title_height,
+        };
+        let layout = fit_grid(&puzzle.grid, &grid_area);
+        self.draw_grid(renderer, page, &puzzle.grid, &layout);
+        self.draw_capsules(renderer, page, &layout, &puzzle.placements);
+    }
+
+    fn check_glyphs<'a>(&self, texts: impl Iterator<Item = &'a str>, warnings: &mut Vec<String>) {
+        for text in texts {
+            let missing = self.fonts.missing_glyphs(text);
+            if !missing.is_empty() {
+                let chars: String = missing.into_iter().collect();
+                warnings.push(format!("a fonte não tem os caracteres \"{}\" usados em \"{}\"", chars, text));
+            }
+        }
+    }
+
+    fn text_width(&self, text: &str, font_size: f32) -> f32 {
+        self.fonts.advance_em(text) * font_size * PT_TO_MM
+    }
+
+    fn draw_heading(&self, renderer: &mut dyn Renderer, page: usize, text: &str, warnings: &mut Vec<String>) {
+        if self.text_width(text, HEADING_FONT_SIZE) > self.page.width_mm - 2.0 * MARGIN_MM {
+            warnings.push(format!("o título \"{}\" é mais largo que a página", text));
+        }
+        renderer.text(page, text, MARGIN_MM, self.page.height_mm - MARGIN_MM, HEADING_FONT_SIZE, FontStyle::Bold);
+    }
+
+    fn grid_layout(&self, grid: &Grid, warnings: &mut Vec<String>) -> GridLayout {
+        let area = Area {
+            left: MARGIN_MM,
+            top: self.page.height_mm - MARGIN_MM - HEADER_HEIGHT_MM + 1.0,
+            width: self.page.width_mm - 2.0 * MARGIN_MM,
+            height: self.page.height_mm - 2.0 * MARGIN_MM - HEADER_HEIGHT_MM - MIN_LIST_SPACE_MM,
+        };
+        self.checked_layout(grid, &area, warnings)
+    }
+
+    /// Como `grid_layout`, mas deixa espaço à esquerda e acima para os números das linhas e colunas.
+    fn coordinate_grid_layout(&self, grid: &Grid, warnings: &mut Vec<String>) -> GridLayout {
+        let area = Area {
+            left: MARGIN_MM + COORDINATE_SPACE_MM,
+            top: self.page.height_mm - MARGIN_MM - HEADER_HEIGHT_MM + 1.0 - COORDINATE_SPACE_MM,
+            width: self.page.width_mm - 2.0 * MARGIN_MM - COORDINATE_SPACE_MM,
+            height: self.page.height_mm - 2.0 * MARGIN_MM - HEADER_HEIGHT_MM - MIN_LIST_SPACE_MM - COORDINATE_SPACE_MM,
+        };
+        self.checked_layout(grid, &area, warnings)
+    }
+
+    fn checked_layout(&self, grid: &Grid, area: &Area, warnings: &mut Vec<String>) -> GridLayout {
+        let layout = fit_grid(grid, area);
+
+        if layout.cell < MIN_CELL_SIZE_MM {
+            warnings.push(format!(
+                "o grid {}x{} fica com células de {:.1} mm nesta página; considere uma página maior",
+                grid.width, grid.height, layout.cell
+            ));
+        }
+        layout
+    }
+
+    fn draw_grid(&self, renderer: &mut dyn Renderer, page: usize, grid: &Grid, layout: &GridLayout) {
+        for (row_idx, row) in grid.cells.iter().enumerate() {
+            for (col_idx, cell) in row.iter().enumerate() {
+                if !grid.is_active(row_idx, col_idx) {
+                    continue;
+                }
+                let Some(letter) = cell else {
+                    continue;
+                };
+                let x = layout.cell_x(col_idx);
+                let y = layout.cell_top(row_idx);
+
+                let ch = letter.to_string();
+                let text_x = x + (layout.cell - self.text_width(&ch, layout.font_size)) / 2.0;
+                let text_y = y - layout.cell * 0.8;
+                renderer.text(page, &ch, text_x, text_y, layout.font_size, FontStyle::Regular);
+            }
+        }
+
+        self.draw_grid_lines(renderer, page, grid, layout);
+    }
+
+    fn draw_coordinates(&self, renderer: &mut dyn Renderer, page: usize, grid: &Grid, layout: &GridLayout) {
+        let size = (layout.font_size * 0.6).min(LIST_FONT_SIZE);
+        for col in 0..grid.width {
+            let label = (col + 1).to_string();
+            let x = layout.cell_x(col) + (layout.cell - self.text_width(&label, size)) / 2.0;
+            renderer.text(page, &label, x, layout.top + COORDINATE_GAP_MM, size, FontStyle::Regular);
+        }
+        for row in 0..grid.height {
+            let label = (row + 1).to_string();
+            let x = layout.left - COORDINATE_GAP_MM - self.text_width(&label, size);
+            renderer.text(page, &label, x, layout.cell_top(row) - layout.cell * 0.7, size, FontStyle::Regular);
+        }
+    }
+
+    fn draw_capsules(&self, renderer: &mut dyn Renderer, page: usize, layout: &GridLayout, placements: &[PlacementResult]) {
+        for (index, placement) in placements.iter().enumerate() {
+            let positions = placement.positions();
+            let (Some(&first), Some(&last)) = (positions.first(), positions.last()) else {
+                continue;
+            };
+
+            let start = layout.cell_center(first.0, first.1);
+            let end = layout.cell_center(last.0, last.1);
+            let points = capsule_outline(start, end, layout.cell * CAPSULE_RADIUS_RATIO);
+            renderer.stroke(page, &points, true, &self.capsule_stroke(index, layout.cell * 0.08));
+        }
+    }
+
+    fn capsule_stroke(&self, index: usize, width_mm: f32) -> Stroke {
+        match self.answer_style {
+            AnswerStyle::Color => {
+                let (r, g, b) = PALETTE[index % PALETTE.len()];
+                Stroke::solid(Color::Rgb(r, g, b), width_mm)
+            }
+            AnswerStyle::Grayscale => {
+                let level = GRAY_LEVELS[index % GRAY_LEVELS.len()];
+                let dash = match (index / GRAY_LEVELS.len()) % 3 {
+                    0 => None,
+                    1 => Some((1.4, 0.7)),
+                    _ => Some((0.35, 0.7)),
+                };
+                Stroke { color: Color::Gray(level), width_mm, dash }
+            }
+        }
+    }
+
+    fn draw_grid_lines(&self, renderer: &mut dyn Renderer, page: usize, grid: &Grid, layout: &GridLayout) {
+        let mut inner = Vec::new();
+        let mut outline = Vec::new();
+
+        for row in 0..grid.height {
+            for col in 0..grid.width {
+                if !grid.is_active(row, col) {
+                    continue;
+                }
+                let x0 = layout.cell_x(col);
+                let x1 = x0 + layout.cell;
+                let y0 = layout.cell_top(row);
+                let y1 = y0 - layout.cell;
+
+                if row == 0 || !grid.is_active(row - 1, col) {
+                    outline.push([(x0, y0), (x1, y0)]);
+                }
+                if col == 0 || !grid.is_active(row, col - 1) {
+                    outline.push([(x0, y0), (x0, y1)]);
+                }
+                if grid.is_active(row + 1, col) {
+                    inner.push([(x0, y1), (x1, y1)]);
+                } else {
+                    outline.push([(x0, y1), (x1, y1)]);
+                }
+                if grid.is_active(row, col + 1) {
+                    inner.push([(x1, y0), (x1, y1)]);
+                } else {
+                    outline.push([(x1, y0), (x1, y1)]);
+                }
+            }
+        }
+
+        let inner_stroke = Stroke::solid(BLACK, INNER_LINE_MM);
+        for segment in inner {
+            renderer.stroke(page, &segment, false, &inner_stroke);
+        }
+        let outline_stroke = Stroke::solid(BLACK, OUTLINE_MM);
+        for segment in outline {
+            renderer.stroke(page, &segment, false, &outline_stroke);
+        }
+    }
+
+    fn draw_list(&self, renderer: &mut dyn Renderer, first_page: usize, list: ListPages, warnings: &mut Vec<String>) -> Vec<usize> {
+        let col_width = (self.page.width_mm - 2.0 * MARGIN_MM) / LIST_COLUMNS as f32;
+        for item in list.items {
+            if self.text_width(item, LIST_FONT_SIZE) > col_width {
+                warnings.push(format!("\"{}\" é mais largo que a coluna da lista e pode se sobrepor", item));
+            }
+        }
+
+        let mut pages = vec![first_page];
+        let mut page = first_page;
+        let mut label_y = list.start_y;
+        let mut remaining = list.items;
+        let mut drawn = 0;
+
+        loop {
+            let rows = list_rows_available(label_y);
+            if rows > 0 {
+                let label = if drawn > 0 {
+                    format!("{} (continuação)", list.label)
+                } else {
+                    list.label.to_string()
+                };
+                renderer.text(page, &label, MARGIN_MM, label_y, LIST_FONT_SIZE, FontStyle::Regular);
+
+                let take = remaining.len().min(rows * LIST_COLUMNS);
+                for (i, item) in remaining[..take].iter().enumerate() {
+                    let x = MARGIN_MM + ((i % LIST_COLUMNS) as f32 * col_width);
+                    let y = label_y - 8.0 - ((i / LIST_COLUMNS) as f32 * LIST_LINE_HEIGHT_MM);
+                    renderer.text(page, item, x, y, LIST_FONT_SIZE, FontStyle::Regular);
+                }
+                remaining = &remaining[take..];
+                drawn += take;
+            }
+
+            if remaining.is_empty() {
+                break;
+            }
+
+            page = renderer.add_page();
+            self.draw_heading(renderer, page, &format!("{} (continuação)", list.heading), &mut Vec::new());
+            pages.push(page);
+            label_y = self.page.height_mm - MARGIN_MM - HEADER_HEIGHT_MM;
+        }
+
+        pages
+    }
+
+    fn draw_footers(&self, renderer: &mut dyn Renderer, pages: &[(usize, Option<u64>)]) {
+        for (i, &(page, seed)) in pages.iter().enumerate() {
+            if let Some(seed) = seed {
+                let text = format!("Seed: {}", seed);
+                renderer.text(page, &text, MARGIN_MM, MARGIN_MM / 2.0, FOOTER_FONT_SIZE, FontStyle::Regular);
+            }
+            if pages.len() > 1 {
+                let text = format!("Página {} de {}", i + 1, pages.len());
+                let width = self.text_width(&text, FOOTER_FONT_SIZE);
+                renderer.text(page, &text, self.page.width_mm - MARGIN_MM - width, MARGIN_MM / 2.0, FOOTER_FONT_SIZE, FontStyle::Regular);
+            }
+        }
+    }
+}
+
+/// Caminho de saída da página `index` (a partir de 0) para formatos de uma página por arquivo:
+/// `puzzle.svg`, `puzzle-2.svg`, `puzzle-3.svg`...
+pub fn page_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+    let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+    let name = match path.extension() {
+        Some(ext) => format!("{}-{}.{}", stem, index + 1, ext.to_string_lossy()),
+        None => format!("{}-{}", stem, index + 1),
+    };
+    path.with_file_name(name)
+}
+
+fn list_rows_available(label_y: f32) -> usize {
+    let first_row_y = label_y - 8.0;
+    if first_row_y <= MARGIN_MM {
+        return 0;
+    }
+    ((first_row_y - MARGIN_MM) / LIST_LINE_HEIGHT_MM).ceil() as usize
+}
+
+fn fit_grid(grid: &Grid, area: &Area) -> GridLayout {
+    let cell = (area.width / grid.width as f32)
+        .min(area.height / grid.height as f32)
+        .min(MAX_CELL_SIZE_MM);
+    GridLayout {
+        left: area.left + (area.width - grid.width as f32 * cell) / 2.0,
+        top: area.top,
+        cell,
+        font_size: cell * CELL_FONT_RATIO,
+    }
+}
+
+/// Contorno de uma cápsula (retângulo com pontas arredondadas) entre os centros da primeira e da
+/// última letra, aproximado por segmentos.
+fn capsule_outline(start: (f32, f32), end: (f32, f32), radius: f32) -> Vec<(f32, f32)> {
+    let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+    let axis = if dx == 0.0 && dy == 0.0 { 0.0 } else { dy.atan2(dx) };
+    let normal = axis + std::f32::consts::FRAC_PI_2;
+
+    let arc = |(cx, cy): (f32, f32), from: f32| {
+        (0..=CAPSULE_ARC_STEPS).map(move |step| {
+            let angle = from - std::f32::consts::PI * step as f32 / CAPSULE_ARC_STEPS as f32;
+            (cx + radius * angle.cos(), cy + radius * angle.sin())
+        })
+    };
+
+    arc(end, normal)
+        .chain(arc(start, normal - std::f32::consts::PI))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::direction::Direction;
+    use crate::word::Word;
+
+    /// Guarda o que foi desenhado, página por página, sem gerar arquivo.
+    #[derive(Default)]
+    struct Recorder {
+        pages: usize,
+        texts: Vec<(usize, String)>,
+        closed_strokes: Vec<Stroke>,
+    }
+
+    impl Renderer for Recorder {
+        fn add_page(&mut self) -> usize {
+            self.pages += 1;
+            self.pages - 1
+        }
+
+        fn text(&mut self, page: usize, text: &str, _x: f32, _y: f32, _size_pt: f32, _style: FontStyle) {
+            self.texts.push((page, text.to_string()));
+        }
+
+        fn stroke(&mut self, _page: usize, _points: &[(f32, f32)], closed: bool, stroke: &Stroke) {
+            if closed {
+                self.closed_strokes.push(*stroke);
+            }
+        }
+
+        fn save(self: Box<Self>, _path: &Path) -> Result<Vec<PathBuf>, String> {
+            Ok(Vec::new())
+        }
+    }
+
+    impl Recorder {
+        fn new() -> Self {
+            Self { pages: 1, ..Self::default() }
+        }
+
+        fn count(&self, text: &str) -> usize {
+            self.texts.iter().filter(|(_, t)| t == text).count()
+        }
+    }
+
+    #[test]
+    fn long_word_lists_continue_on_new_pages() {
+        let words: Vec<String> = (0..200).map(|i| format!("PALAVRA{}", i)).collect();
+        let mut recorder = Recorder::new();
+        let report = PageComposer::new("Teste").with_seed(9).compose_puzzle(&mut recorder, &Grid::new(12, 12), &words);
+
+        assert!(report.pages > 1);
+        assert_eq!(recorder.pages, report.pages);
+        assert!(words.iter().all(|word| recorder.count(word) == 1));
+        assert_eq!(recorder.count("Seed: 9"), report.pages);
+        assert_eq!(recorder.count(&format!("Página {} de {}", report.pages, report.pages)), 1);
+        assert!(recorder.texts.iter().any(|(page, t)| *page == 1 && t.ends_with("(continuação)")));
+    }
+
+    #[test]
+    fn short_lists_fit_on_one_page() {
+        let words = vec!["PIZZA".to_string(), "QUEIJO".to_string()];
+        let mut recorder = Recorder::new();
+        let report = PageComposer::new("Teste").compose_puzzle(&mut recorder, &Grid::new(12, 12), &words);
+        assert_eq!(report.pages, 1);
+        assert!(!recorder.texts.iter().any(|(_, t)| t.starts_with("Página")));
+    }
+
+    #[test]
+    fn warns_about_characters_missing_from_the_font() {
+        let words = vec!["MAÇÃ".to_string(), "猫".to_string()];
+        let report = PageComposer::new("Frutas").compose_puzzle(&mut Recorder::new(), &Grid::new(12, 12), &words);
+        assert_eq!(report.warnings.iter().filter(|w| w.contains('猫')).count(), 1);
+        assert!(!report.warnings.iter().any(|w| w.contains('Ç')));
+    }
+
+    #[test]
+    fn capsules_keep_a_constant_distance_from_the_word_axis() {
+        let radius = 2.0;
+        for (start, end) in [((0.0, 0.0), (10.0, 0.0)), ((0.0, 0.0), (6.0, -6.0)), ((3.0, 3.0), (3.0, 3.0))] {
+            let points = capsule_outline(start, end, radius);
+            assert_eq!(points.len(), 2 * (CAPSULE_ARC_STEPS + 1));
+            for &(x, y) in &points {
+                let (dx, dy) = (end.0 - start.0, end.1 - start.1);
+                let length = dx * dx + dy * dy;
+                let t = match length {
+                    0.0 => 0.0,
+                    _ => (((x - start.0) * dx + (y - start.1) * dy) / length).clamp(0.0, 1.0),
+                };
+                let (px, py) = (start.0 + t * dx, start.1 + t * dy);
+                assert!((((x - px).powi(2) + (y - py).powi(2)).sqrt() - radius).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn answer_key_outlines_each_word_with_its_own_style() {
+        let mut grid = Grid::new(8, 8);
+        let words = [("PIZZA", 0, 0, Direction::Horizontal), ("FORNO", 1, 0, Direction::DiagonalDown), ("SAL", 7, 7, Direction::VerticalReverse)];
+        let placements: Vec<PlacementResult> = words
+            .iter()
+            .map(|&(word, row, col, direction)| grid.place_word(&Word::new(word), row, col, direction))
+            .collect();
+
+        for style in [AnswerStyle::Color, AnswerStyle::Grayscale] {
+            let composer = PageComposer::new("Teste").with_answer_style(style);
+            let capsules: Vec<Stroke> = (0..placements.len()).map(|i| composer.capsule_stroke(i, 0.5)).collect();
+            let mut recorder = Recorder::new();
+            composer.compose_answer_key(&mut recorder, &grid, &placements, None);
+            let drawn: Vec<_> = recorder.closed_strokes.iter().map(|s| (s.color, s.dash)).collect();
+            let expected: Vec<_> = capsules.iter().map(|s| (s.color, s.dash)).collect();
+            assert_eq!(drawn, expected, "{:?}", style);
+            assert!(capsules.iter().enumerate().all(|(i, a)| capsules[..i].iter().all(|b| a.color != b.color)));
+        }
+    }
+
+    #[test]
+    fn grayscale_style_never_uses_color_and_varies_dashes() {
+        let composer = PageComposer::new("Teste").with_answer_style(AnswerStyle::Grayscale);
+        let strokes: Vec<Stroke> = (0..9).map(|i| composer.capsule_stroke(i, 0.5)).collect();
+        assert!(strokes.iter().all(|s| matches!(s.color, Color::Gray(_))));
+        assert!(strokes.iter().enumerate().all(|(i, a)| strokes[..i].iter().all(|b| a != b)));
+    }
+
+    #[test]
+    fn booklet_has_contents_puzzles_and_answer_keys_at_the_back() {
+        let puzzles: Vec<PuzzleFile> = (0..5)
+            .map(|i| {
+                let words = vec!["PIZZA".to_string()];
+                PuzzleFile::new(&format!("Puzzle {}", i + 1), i, crate::difficulty::Difficulty::easy(), words, Grid::new(8, 8), Vec::new())
+            })
+            .collect();
+        let mut recorder = Recorder::new();
+        let report = PageComposer::new("Caderno").compose_booklet(&mut recorder, &puzzles, 4);
+
+        // Sumário, um puzzle por página e os cinco gabaritos em duas páginas.
+        assert_eq!(report.pages, 1 + 5 + 2);
+        assert_eq!(recorder.pages, report.pages);
+        assert_eq!(recorder.count("Sumário"), 1);
+        assert_eq!(recorder.count("Gabaritos"), 2 + 1);
+        let first_key_page = recorder.texts.iter().find(|(_, t)| t == "Gabaritos").map(|(page, _)| *page);
+        assert_eq!(first_key_page, Some(6));
+    }
+
+    #[test]
+    fn extra_pages_get_numbered_file_names() {
+        assert_eq!(page_path(Path::new("saida/puzzle.svg"), 0), Path::new("saida/puzzle.svg"));
+        assert_eq!(page_path(Path::new("saida/puzzle.svg"), 2), Path::new("saida/puzzle-3.svg"));
+    }
+
+    #[test]
+    fn smallest_page_leaves_room_for_a_readable_grid() {
+        let size = PageSize::Custom { width_mm: MIN_PAGE_SIDE_MM, height_mm: MIN_PAGE_SIDE_MM };
+        let grid = Grid::new(10, 10);
+        for orientation in [Orientation::Portrait, Orientation::Landscape] {
+            let composer = PageComposer::new("Teste").with_page(PageFormat::new(size, orientation));
+            let mut warnings = Vec::new();
+            let plain = composer.grid_layout(&grid, &mut warnings);
+            let numbered = composer.coordinate_grid_layout(&grid, &mut warnings);
+            assert!(plain.cell >= MIN_CELL_SIZE_MM && numbered.cell >= MIN_CELL_SIZE_MM);
+            assert!(warnings.is_empty(), "{:?}", warnings);
+        }
+    }
+
+    #[test]
+    fn grid_scales_down_to_fit_and_never_past_the_maximum() {
+        let composer = PageComposer::new("Teste");
+        let mut warnings = Vec::new();
+        assert_eq!(composer.grid_layout(&Grid::new(5, 5), &mut warnings).cell, MAX_CELL_SIZE_MM);
+        let layout = composer.grid_layout(&Grid::new(40, 40), &mut warnings);
+        assert!(layout.cell < MAX_CELL_SIZE_MM && layout.cell * 40.0 <= PageFormat::default().width_mm - 2.0 * MARGIN_MM);
+    }
+}
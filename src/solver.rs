@@ -0,0 +1,169 @@
+use aho_corasick::AhoCorasick;
+
+use crate::direction::Direction;
+use crate::grid::{Grid, PlacementResult};
+use crate::word::Word;
+
+const LINE_DIRECTIONS: [(Direction, Direction); 4] = [
+    (Direction::Horizontal, Direction::HorizontalReverse),
+    (Direction::Vertical, Direction::VerticalReverse),
+    (Direction::DiagonalDown, Direction::DiagonalDownReverse),
+    (Direction::DiagonalUp, Direction::DiagonalUpReverse),
+];
+
+pub struct Solver {
+    words: Vec<Word>,
+    automaton: AhoCorasick,
+}
+
+impl Solver {
+    pub fn new(words: &[Word]) -> Self {
+        let words: Vec<Word> = words.iter().filter(|w| !w.is_empty()).cloned().collect();
+        let forward = words.iter().map(|w| w.normalized.clone());
+        let reversed = words.iter().map(|w| w.normalized.chars().rev().collect::<String>());
+        let patterns: Vec<String> = forward.chain(reversed).collect();
+        let automaton = AhoCorasick::new(&patterns).expect("padrões ASCII sempre são válidos");
+        Self { words, automaton }
+    }
+
+    pub fn words(&self) -> &[Word] {
+        &self.words
+    }
+
+    pub fn find_grouped(&self, grid: &Grid) -> Vec<Vec<PlacementResult>> {
+        let mut found = vec![Vec::new(); self.words.len()];
+        let word_count = self.words.len();
+
+        for (forward, backward) in LINE_DIRECTIONS {
+            for line in grid_lines(grid, forward) {
+                // Um byte por célula: os offsets do Aho-Corasick viram índices de `line`.
+                let text: String = line
+                    .iter()
+                    .map(|&(row, col)| match grid.get(row, col) {
+                        Some(letter) if letter.is_ascii_uppercase() => letter,
+                        _ => '.',
+                    })
+                    .collect();
+
+                for m in self.automaton.find_overlapping_iter(&text) {
+                    let pattern = m.pattern().as_usize();
+                    if pattern < word_count {
+                        if self.words[pattern].len() == 1 && forward != Direction::Horizontal {
+                            continue;
+                        }
+                        let (row, col) = line[m.start()];
+                        found[pattern].push(self.occurrence(pattern, row, col, forward));
+                    } else {
+                        let index = pattern - word_count;
+                        if is_palindrome(&self.words[index]) {
+                            continue;
+                        }
+                        let (row, col) = line[m.end() - 1];
+                        found[index].push(self.occurrence(index, row, col, backward));
+                    }
+                }
+            }
+        }
+
+        found
+    }
+
+    fn occurrence(&self, index: usize, row: usize, col: usize, direction: Direction) -> PlacementResult {
+        PlacementResult {
+            word: self.words[index].clone(),
+            row,
+            col,
+            direction,
+        }
+    }
+}
+
+fn is_palindrome(word: &Word) -> bool {
+    word.normalized.chars().eq(word.normalized.chars().rev())
+}
+
+fn grid_lines(grid: &Grid, direction: Direction) -> Vec<Vec<(usize, usize)>> {
+    let (dr, dc) = direction.deltas();
+    let (width, height) = (grid.width as i32, grid.height as i32);
+    let in_bounds = |row: i32, col: i32| row >= 0 && col >= 0 && row < height && col < width;
+
+    let mut lines = Vec::new();
+    for row in 0..height {
+        for col in 0..width {
+            if in_bounds(row - dr, col - dc) {
+                continue;
+            }
+            let mut line = Vec::new();
+            let (mut r, mut c) = (row, col);
+            while in_bounds(r, c) {
+                line.push((r as usize, c as usize));
+                r += dr;
+                c += dc;
+            }
+            lines.push(line);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solve(rows: &str, words: &[&str]) -> Vec<Vec<PlacementResult>> {
+        let grid = Grid::from_text(rows).unwrap();
+        let words: Vec<Word> = words.iter().map(|w| Word::new(w)).collect();
+        Solver::new(&words).find_grouped(&grid)
+    }
+
+    fn starts(found: &[PlacementResult]) -> Vec<(usize, usize, Direction)> {
+        found.iter().map(|p| (p.row, p.col, p.direction)).collect()
+    }
+
+    #[test]
+    fn finds_forward_and_reversed_words() {
+        let found = solve("QUEIJO\nXXXXXX\nOHLOMX", &["QUEIJO", "MOLHO"]);
+        assert_eq!(starts(&found[0]), vec![(0, 0, Direction::Horizontal)]);
+        assert_eq!(starts(&found[1]), vec![(2, 4, Direction::HorizontalReverse)]);
+    }
+
+    #[test]
+    fn reports_the_first_letter_of_vertical_and_diagonal_reversed_hits() {
+        let found = solve("XXA\nXXZ\nXXZ\nXXI\nXXP", &["PIZZA"]);
+        assert_eq!(starts(&found[0]), vec![(4, 2, Direction::VerticalReverse)]);
+
+        let found = solve("CXX\nXAX\nXXL", &["LAC"]);
+        assert_eq!(starts(&found[0]), vec![(2, 2, Direction::DiagonalDownReverse)]);
+    }
+
+    #[test]
+    fn counts_palindromes_once() {
+        let found = solve("XRADARX\nXXXXXXX", &["RADAR"]);
+        assert_eq!(starts(&found[0]), vec![(0, 1, Direction::Horizontal)]);
+    }
+
+    #[test]
+    fn matches_single_letter_words_only_horizontally() {
+        let found = solve("AX\nXA", &["A"]);
+        assert_eq!(starts(&found[0]), vec![(0, 0, Direction::Horizontal), (1, 1, Direction::Horizontal)]);
+    }
+
+    #[test]
+    fn masked_cells_break_lines() {
+        let found = solve("PI.ZA\nXXXXX", &["PIZA"]);
+        assert!(found[0].is_empty());
+
+        let found = solve("PIZA.\nXXXXX", &["PIZA"]);
+        assert_eq!(starts(&found[0]), vec![(0, 0, Direction::Horizontal)]);
+    }
+
+    #[test]
+    fn non_ascii_cells_do_not_shift_positions() {
+        let mut grid = Grid::new(15, 1);
+        for (col, letter) in "ÇÇÇÇÇÇÇÇÇÇAZZIP".chars().enumerate() {
+            grid.set(0, col, letter);
+        }
+        let found = Solver::new(&[Word::new("PIZZA")]).find_grouped(&grid);
+        assert_eq!(starts(&found[0]), vec![(0, 14, Direction::HorizontalReverse)]);
+    }
+}
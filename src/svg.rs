@@ -0,0 +1,203 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::font::{FontSet, PathOp};
+use crate::render::{page_path, Color, FontStyle, PageFormat, Renderer, Stroke, PT_TO_MM};
+
+pub struct SvgRenderer {
+    page: PageFormat,
+    fonts: FontSet,
+    glyphs: HashMap<(FontStyle, char), (String, f32)>,
+    pages: Vec<SvgPage>,
+}
+
+#[derive(Default)]
+struct SvgPage {
+    body: String,
+    used: BTreeMap<(FontStyle, char), String>,
+}
+
+impl SvgRenderer {
+    pub fn new(page: PageFormat, fonts: &FontSet) -> Self {
+        Self {
+            page,
+            fonts: fonts.clone(),
+            glyphs: HashMap::new(),
+            pages: vec![SvgPage::default()],
+        }
+    }
+
+    fn glyph(&mut self, style: FontStyle, ch: char) -> Option<&(String, f32)> {
+        if !self.glyphs.contains_key(&(style, ch)) {
+            let (ops, advance) = self.fonts.glyph_outline(style, ch)?;
+            self.glyphs.insert((style, ch), (path_data(&ops), advance));
+        }
+        self.glyphs.get(&(style, ch))
+    }
+
+    fn to_svg(&self, page: &SvgPage) -> String {
+        let (width, height) = (self.page.width_mm, self.page.height_mm);
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink" width="{w}mm" height="{h}mm" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        );
+        svg.push_str("<defs>\n");
+        for (key, id) in &page.used {
+            if let Some((d, _)) = self.glyphs.get(key) {
+                let _ = writeln!(svg, r#"<path id="{}" d="{}"/>"#, id, d);
+            }
+        }
+        svg.push_str("</defs>\n");
+        let _ = writeln!(svg, r#"<rect width="{}" height="{}" fill="white"/>"#, width, height);
+        svg.push_str(&page.body);
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn add_page(&mut self) -> usize {
+        self.pages.push(SvgPage::default());
+        self.pages.len() - 1
+    }
+
+    fn text(&mut self, page: usize, text: &str, x: f32, y: f32, size_pt: f32, style: FontStyle) {
+        let scale = size_pt * PT_TO_MM;
+        let baseline = self.page.height_mm - y;
+        let mut pen = x;
+
+        for ch in text.chars() {
+            let Some((d, advance)) = self.glyph(style, ch).cloned() else {
+                continue;
+            };
+            if !d.is_empty() {
+                let id = glyph_id(style, ch);
+                let svg_page = &mut self.pages[page];
+                svg_page.used.insert((style, ch), id.clone());
+                let _ = writeln!(
+                    svg_page.body,
+                    r##"<use xlink:href="#{}" transform="translate({:.3} {:.3}) scale({:.4} {:.4})"/>"##,
+                    id, pen, baseline, scale, -scale
+                );
+            }
+            pen += advance * scale;
+        }
+    }
+
+    fn stroke(&mut self, page: usize, points: &[(f32, f32)], closed: bool, stroke: &Stroke) {
+        let height = self.page.height_mm;
+        let coords: Vec<String> = points
+            .iter()
+            .map(|&(x, y)| format!("{:.3},{:.3}", x, height - y))
+            .collect();
+        let element = if closed { "polygon" } else { "polyline" };
+        let dash = match stroke.dash {
+            Some((dash, gap)) => format!(r#" stroke-dasharray="{:.2} {:.2}""#, dash, gap),
+            None => String::new(),
+        };
+        let _ = writeln!(
+            self.pages[page].body,
+            r#"<{} points="{}" fill="none" stroke="{}" stroke-width="{:.3}" stroke-linejoin="round"{}/>"#,
+            element,
+            coords.join(" "),
+            css_color(stroke.color),
+            stroke.width_mm,
+            dash
+        );
+    }
+
+    fn save(self: Box<Self>, path: &Path) -> Result<Vec<PathBuf>, String> {
+        let mut written = Vec::new();
+        for (index, page) in self.pages.iter().enumerate() {
+            let page_file = page_path(path, index);
+            fs::write(&page_file, self.to_svg(page)).map_err(|e| format!("{}: {}", page_file.display(), e))?;
+            written.push(page_file);
+        }
+        Ok(written)
+    }
+}
+
+fn glyph_id(style: FontStyle, ch: char) -> String {
+    match style {
+        FontStyle::Regular => format!("g{:x}", ch as u32),
+        FontStyle::Bold => format!("b{:x}", ch as u32),
+    }
+}
+
+fn path_data(ops: &[PathOp]) -> String {
+    let mut d = String::new();
+    for op in ops {
+        let _ = match *op {
+            PathOp::MoveTo(x, y) => write!(d, "M{:.4} {:.4}", x, y),
+            PathOp::LineTo(x, y) => write!(d, "L{:.4} {:.4}", x, y),
+            PathOp::QuadTo(cx, cy, x, y) => write!(d, "Q{:.4} {:.4} {:.4} {:.4}", cx, cy, x, y),
+            PathOp::CubicTo(x1, y1, x2, y2, x, y) => {
+                write!(d, "C{:.4} {:.4} {:.4} {:.4} {:.4} {:.4}", x1, y1, x2, y2, x, y)
+            }
+            PathOp::Close => write!(d, "Z"),
+        };
+    }
+    d
+}
+
+fn css_color(color: Color) -> String {
+    let channel = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    match color {
+        Color::Rgb(r, g, b) => format!("rgb({},{},{})", channel(r), channel(g), channel(b)),
+        Color::Gray(level) => {
+            let v = channel(level);
+            format!("rgb({},{},{})", v, v, v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: PageFormat = PageFormat { width_mm: 100.0, height_mm: 50.0 };
+
+    #[test]
+    fn repeated_letters_share_one_glyph_definition() {
+        let mut renderer = SvgRenderer::new(PAGE, &FontSet::bundled());
+        renderer.text(0, "PIZZA", 10.0, 10.0, 12.0, FontStyle::Regular);
+        let svg = renderer.to_svg(&renderer.pages[0]);
+
+        assert_eq!(svg.matches(r#"<path id="g5a""#).count(), 1);
+        assert_eq!(svg.matches("xlink:href=\"#g5a\"").count(), 2);
+        assert_eq!(svg.matches("<use ").count(), 5);
+    }
+
+    #[test]
+    fn strokes_flip_the_y_axis_and_keep_dashes() {
+        let mut renderer = SvgRenderer::new(PAGE, &FontSet::bundled());
+        let dashed = Stroke { color: Color::Rgb(1.0, 0.0, 0.0), width_mm: 0.5, dash: Some((2.0, 1.0)) };
+        renderer.stroke(0, &[(0.0, 0.0), (10.0, 20.0)], false, &dashed);
+        let svg = renderer.to_svg(&renderer.pages[0]);
+
+        assert!(svg.contains(r#"<polyline points="0.000,50.000 10.000,30.000""#), "{}", svg);
+        assert!(svg.contains(r#"stroke="rgb(255,0,0)""#));
+        assert!(svg.contains(r#"stroke-dasharray="2.00 1.00""#));
+    }
+
+    #[test]
+    fn each_page_is_saved_to_its_own_file() {
+        let path = std::env::temp_dir().join(format!("pizza-wordsearch-{}.svg", std::process::id()));
+        let mut renderer = SvgRenderer::new(PAGE, &FontSet::bundled());
+        let second = renderer.add_page();
+        renderer.text(second, "B", 10.0, 10.0, 12.0, FontStyle::Bold);
+
+        let files = Box::new(renderer).save(&path).unwrap();
+        let contents: Vec<String> = files.iter().map(|f| fs::read_to_string(f).unwrap()).collect();
+        files.iter().for_each(|f| fs::remove_file(f).unwrap());
+
+        assert_eq!(files, vec![path.clone(), page_path(&path, 1)]);
+        assert!(contents.iter().all(|svg| svg.starts_with("<svg ") && svg.ends_with("</svg>\n")));
+        assert!(!contents[0].contains("<use ") && contents[1].contains(r#"<path id="b42""#));
+    }
+}
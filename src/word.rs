@@ -1,6 +1,7 @@
+use serde::{Deserialize, Serialize};
 use unicode_normalization::UnicodeNormalization;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Word {
     pub original: String,
     pub normalized: String,
@@ -31,5 +32,5 @@ impl Word {
 }
 
 pub fn sort_by_length_desc(words: &mut [Word]) {
-    words.sort_by(|a, b| b.len().cmp(&a.len()));
+    words.sort_by_key(|w| std::cmp::Reverse(w.len()));
 }
@@ -0,0 +1,227 @@
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::word::Word;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordEntry {
+    pub word: String,
+    pub clue: Option<String>,
+    pub category: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonEntry {
+    Plain(String),
+    Detailed {
+        #[serde(alias = "palavra")]
+        word: String,
+        #[serde(default, alias = "dica")]
+        clue: Option<String>,
+        #[serde(default, alias = "categoria")]
+        category: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonList {
+    Entries(Vec<JsonEntry>),
+    Wrapped {
+        #[serde(alias = "palavras")]
+        words: Vec<JsonEntry>,
+    },
+}
+
+impl WordEntry {
+    fn new(word: &str) -> Self {
+        Self {
+            word: word.trim().to_string(),
+            clue: None,
+            category: None,
+        }
+    }
+}
+
+pub fn load_word_list(path: &Path) -> io::Result<Vec<WordEntry>> {
+    let content = fs::read_to_string(path)?;
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+
+    let entries = match extension.as_deref() {
+        Some("csv") => parse_csv(&content),
+        Some("json") => parse_json(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+        _ => parse_text(&content),
+    };
+
+    Ok(dedup_entries(entries))
+}
+
+pub fn parse_text(content: &str) -> Vec<WordEntry> {
+    content_lines(content)
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|w| !w.is_empty())
+        .map(WordEntry::new)
+        .collect()
+}
+
+pub fn parse_csv(content: &str) -> Vec<WordEntry> {
+    let mut lines = content_lines(content).peekable();
+    let delimiter = match lines.peek() {
+        Some(first) if first.contains(';') && !first.contains(',') => ';',
+        _ => ',',
+    };
+
+    let mut entries = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let fields = split_csv_line(line, delimiter);
+        let word = fields.first().map(|f| f.trim()).unwrap_or("");
+        if i == 0 && is_header(word) {
+            continue;
+        }
+        if word.is_empty() {
+            continue;
+        }
+
+        let optional = |index: usize| {
+            fields
+                .get(index)
+                .map(|f| f.trim().to_string())
+                .filter(|f| !f.is_empty())
+        };
+        entries.push(WordEntry {
+            word: word.to_string(),
+            clue: optional(1),
+            category: optional(2),
+        });
+    }
+    entries
+}
+
+pub fn parse_json(content: &str) -> Result<Vec<WordEntry>, String> {
+    let list: JsonList = serde_json::from_str(strip_bom(content)).map_err(|e| e.to_string())?;
+    let entries = match list {
+        JsonList::Entries(entries) | JsonList::Wrapped { words: entries } => entries,
+    };
+
+    Ok(entries
+        .into_iter()
+        .map(|entry| match entry {
+            JsonEntry::Plain(word) => WordEntry::new(&word),
+            JsonEntry::Detailed { word, clue, category } => WordEntry {
+                clue,
+                category,
+                ..WordEntry::new(&word)
+            },
+        })
+        .filter(|entry| !entry.word.is_empty())
+        .collect())
+}
+
+pub fn dedup_entries(entries: Vec<WordEntry>) -> Vec<WordEntry> {
+    let mut seen = HashSet::new();
+    entries
+        .into_iter()
+        .filter(|entry| {
+            let word = Word::new(&entry.word);
+            !word.is_empty() && seen.insert(word.normalized)
+        })
+        .collect()
+}
+
+pub fn dedup_words(words: Vec<String>) -> Vec<String> {
+    let entries = words.iter().map(|w| WordEntry::new(w)).collect();
+    dedup_entries(entries).into_iter().map(|entry| entry.word).collect()
+}
+
+fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{feff}').unwrap_or(content)
+}
+
+fn content_lines(content: &str) -> impl Iterator<Item = &str> {
+    strip_bom(content)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#') && !line.starts_with("//"))
+}
+
+fn is_header(field: &str) -> bool {
+    matches!(field.to_lowercase().as_str(), "palavra" | "palavras" | "word" | "words")
+}
+
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            c if c == delimiter && !in_quotes => fields.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(entries: &[WordEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.word.as_str()).collect()
+    }
+
+    #[test]
+    fn text_accepts_lines_commas_and_comments() {
+        let entries = parse_text("\u{feff}pizza, queijo\n# comentário\n// outro\n\n  molho  \n");
+        assert_eq!(words(&entries), vec!["pizza", "queijo", "molho"]);
+    }
+
+    #[test]
+    fn csv_reads_clues_categories_header_and_quotes() {
+        let entries = parse_csv("palavra,dica,categoria\npizza,\"redonda, quente\",prato\n\"mo\"\"lho\",,\nforno\n");
+        assert_eq!(words(&entries), vec!["pizza", "mo\"lho", "forno"]);
+        assert_eq!(entries[0].clue.as_deref(), Some("redonda, quente"));
+        assert_eq!(entries[0].category.as_deref(), Some("prato"));
+        assert_eq!(entries[1].clue, None);
+    }
+
+    #[test]
+    fn csv_detects_semicolons() {
+        let entries = parse_csv("pizza;italiana\nqueijo;mussarela, prato\n");
+        assert_eq!(words(&entries), vec!["pizza", "queijo"]);
+        assert_eq!(entries[1].clue.as_deref(), Some("mussarela, prato"));
+    }
+
+    #[test]
+    fn json_accepts_plain_detailed_and_wrapped_lists() {
+        assert_eq!(words(&parse_json(r#"["pizza", " forno ", ""]"#).unwrap()), vec!["pizza", "forno"]);
+
+        let entries = parse_json(r#"{"palavras": [{"palavra": "pizza", "dica": "redonda"}, "queijo"]}"#).unwrap();
+        assert_eq!(words(&entries), vec!["pizza", "queijo"]);
+        assert_eq!(entries[0].clue.as_deref(), Some("redonda"));
+
+        assert!(parse_json("{\"outra\": 1}").is_err());
+    }
+
+    #[test]
+    fn dedup_compares_normalized_words() {
+        let entries = parse_text("Pizza, PIZZA, pizzá, 123, queijo");
+        assert_eq!(words(&dedup_entries(entries)), vec!["Pizza", "queijo"]);
+        assert_eq!(dedup_words(vec!["Forno".to_string(), "forno".to_string()]), vec!["Forno"]);
+    }
+}